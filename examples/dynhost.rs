@@ -0,0 +1,79 @@
+//! Keeps a DynHost record pointing to the current public address.
+//!
+//! ```text
+//! cargo run --example dynhost -- --zone example.com --sub-domain home
+//! ```
+
+use clap::{Arg, Command};
+use ovh::{
+    client::OvhClient,
+    domain::dynhost::{HttpIpSource, Updater},
+};
+use std::time::Duration;
+
+#[tokio::main]
+async fn main() -> ovh::client::Result<()> {
+    let matches = Command::new("dynhost")
+        .about("Keeps a DynHost record pointing to the current public address")
+        .arg(
+            Arg::new("zone")
+                .long("zone")
+                .takes_value(true)
+                .required(true)
+                .help("DNS zone of the record"),
+        )
+        .arg(
+            Arg::new("sub-domain")
+                .long("sub-domain")
+                .takes_value(true)
+                .default_value("")
+                .help("Sub-domain of the record, empty for the zone apex"),
+        )
+        .arg(
+            Arg::new("interval")
+                .long("interval")
+                .takes_value(true)
+                .default_value("300")
+                .help("Seconds between address checks"),
+        )
+        .arg(
+            Arg::new("ip-url")
+                .long("ip-url")
+                .takes_value(true)
+                .default_value("https://api.ipify.org")
+                .help("Service answering the public address as plain text"),
+        )
+        .arg(
+            Arg::new("config")
+                .long("config")
+                .short('c')
+                .takes_value(true)
+                .help("Configuration file, instead of the environment and default files"),
+        )
+        .get_matches();
+
+    let client = match matches.value_of("config") {
+        Some(path) => OvhClient::from_conf(path)?,
+        None => OvhClient::from_env_or_conf()?,
+    };
+    let zone = matches.value_of("zone").unwrap();
+    let sub_domain = matches.value_of("sub-domain").unwrap();
+    let interval = Duration::from_secs(matches.value_of_t("interval").unwrap_or_else(|e| e.exit()));
+    let source = HttpIpSource::new(matches.value_of("ip-url").unwrap());
+
+    let name = match sub_domain {
+        "" => zone.to_string(),
+        sub_domain => format!("{}.{}", sub_domain, zone),
+    };
+
+    let mut updater = Updater::new(source, zone, sub_domain);
+    loop {
+        match updater.update(&client).await {
+            Ok(Some(ip)) => println!("{} updated to {}", name, ip),
+            Ok(None) => {}
+            // Transient failures are retried on the next check.
+            Err(e) => eprintln!("update failed: {}", e),
+        }
+        tokio::time::sleep(interval).await;
+    }
+}
@@ -1,24 +1,185 @@
 //! Low-level access to the OVH API.
 
-use configparser::ini::Ini;
-use reqwest::{header::HeaderMap, Response};
-use serde::Serialize;
+use reqwest::{header::HeaderMap, Response, StatusCode};
+use tokio::sync::Mutex;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
 use std::{
+    collections::hash_map::RandomState,
     convert::TryInto,
+    hash::{BuildHasher, Hasher},
     path::Path,
-    time::{SystemTime, UNIX_EPOCH},
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
 use std::num::{ParseIntError, TryFromIntError};
 use thiserror::Error;
 
+use auth::Auth;
+use config::Config;
+use limit::Limits;
+
+mod auth;
+mod batch;
+mod builder;
+mod config;
+mod credential;
+mod endpoint;
+mod limit;
+mod pagination;
+mod request;
+mod retry;
+#[cfg(test)]
+mod stub;
+mod task;
+
+pub use builder::OvhClientBuilder;
+pub use credential::{
+    AccessRule, Credential, CredentialRequest, CredentialState, PendingCredential,
+};
+pub use endpoint::{ApiVersion, Endpoint};
+pub use request::RequestBuilder;
+pub use reqwest::Method;
+pub use retry::RetryPolicy;
+pub use task::{Task, TaskOutcome, TaskStatus, WaitOptions};
+
 #[derive(Debug, Error)]
 pub enum Error {
     #[error("Config: {0}")]
     ConfigError(String),
+    #[error("OVH API error: {0}")]
+    ApiError(Box<ApiError>),
+    #[error("Invalid argument: {0}")]
+    ArgumentError(String),
+    /// The consumer key was reported as expired or refused, see
+    /// [`OvhClient::wait_for_credential`].
+    #[error("Consumer key not validated: {0:?}")]
+    CredentialError(CredentialState),
+    #[error("Timed out: {0}")]
+    TimeoutError(String),
     #[error("OVH error: {0}")]
     Error(#[source] Box<dyn std::error::Error + Send + Sync + 'static>),
 }
-type Result<T> = std::result::Result<T, Error>;
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Returns the error sent back by the API, if any.
+    pub fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            Error::ApiError(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Error returned by the OVH API for a non-successful response.
+///
+/// It is decoded from the JSON body sent by the API along with the
+/// `X-Ovh-QueryID` header, which is worth mentioning when contacting
+/// the OVH support.
+#[derive(Debug, Clone, Error)]
+#[error("{status}: {message}")]
+pub struct ApiError {
+    /// HTTP status of the response.
+    pub status: StatusCode,
+    /// Human-readable description of the error.
+    pub message: String,
+    /// Machine-readable error code, e.g. `INVALID_CREDENTIAL`.
+    pub error_code: Option<String>,
+    /// HTTP code as reported in the body, e.g. `403 Forbidden`.
+    pub http_code: Option<String>,
+    /// Additional details about the error.
+    pub details: Option<serde_json::Value>,
+    /// Error class, e.g. `Client::NotFound`.
+    pub class: Option<String>,
+    /// Identifier of the query on the OVH side.
+    pub query_id: Option<String>,
+    /// Delay to wait for before retrying, as sent by the API.
+    pub retry_after: Option<Duration>,
+}
+
+#[derive(Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct ApiErrorBody {
+    message: Option<String>,
+    error_code: Option<String>,
+    http_code: Option<String>,
+    details: Option<serde_json::Value>,
+    class: Option<String>,
+}
+
+impl ApiError {
+    async fn from_response(resp: Response) -> Self {
+        let status = resp.status();
+        let query_id = resp
+            .headers()
+            .get("X-Ovh-QueryID")
+            .and_then(|v| v.to_str().ok())
+            .map(String::from);
+        let retry_after = resp
+            .headers()
+            .get(reqwest::header::RETRY_AFTER)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.trim().parse().ok())
+            .map(Duration::from_secs);
+
+        let text = resp.text().await.unwrap_or_default();
+        let body = serde_json::from_str::<ApiErrorBody>(&text).unwrap_or_default();
+
+        let message = body
+            .message
+            .or_else(|| Some(text).filter(|t| !t.is_empty()))
+            .or_else(|| status.canonical_reason().map(String::from))
+            .unwrap_or_default();
+
+        ApiError {
+            status,
+            message,
+            error_code: body.error_code,
+            http_code: body.http_code,
+            details: body.details,
+            class: body.class,
+            query_id,
+            retry_after,
+        }
+    }
+
+    /// Whether the requested resource does not exist.
+    pub fn is_not_found(&self) -> bool {
+        self.status == StatusCode::NOT_FOUND
+    }
+
+    /// Whether the consumer key is invalid, expired or not validated yet.
+    pub fn is_invalid_credential(&self) -> bool {
+        matches!(
+            self.error_code.as_deref(),
+            Some("INVALID_CREDENTIAL") | Some("NOT_CREDENTIAL")
+        )
+    }
+
+    /// Whether the call was refused.
+    ///
+    /// This includes invalid credentials as well as calls not granted
+    /// to the consumer key.
+    pub fn is_forbidden(&self) -> bool {
+        self.status == StatusCode::FORBIDDEN
+    }
+
+    /// Whether the request timestamp was too far from the server time.
+    pub fn is_invalid_timestamp(&self) -> bool {
+        self.error_code.as_deref() == Some("QUERY_TIME_OUT")
+            || self.message.eq_ignore_ascii_case("query out of time")
+    }
+
+    /// Whether the API throttled the call.
+    pub fn is_rate_limited(&self) -> bool {
+        self.status == StatusCode::TOO_MANY_REQUESTS
+    }
+}
+
+impl From<ApiError> for Error {
+    fn from(value: ApiError) -> Self {
+        Error::ApiError(Box::new(value))
+    }
+}
 
 impl From<reqwest::Error> for Error {
     fn from(value: reqwest::Error) -> Self {
@@ -47,18 +208,6 @@ impl From<serde_json::Error> for Error {
 
 
 
-// Private data
-
-static ENDPOINTS: phf::Map<&'static str, &'static str> = phf::phf_map! {
-    "ovh-eu" => "https://eu.api.ovh.com/1.0",
-    "ovh-us" => "https://api.us.ovhcloud.com/1.0",
-    "ovh-ca" => "https://ca.api.ovh.com/1.0",
-    "kimsufi-eu" => "https://eu.api.kimsufi.com/1.0",
-    "kimsufi-ca" => "https://ca.api.kimsufi.com/1.0",
-    "soyoustart-eu" => "https://eu.api.soyoustart.com/1.0",
-    "soyoustart-ca" => "https://ca.api.soyoustart.com/1.0",
-};
-
 // Private helpers
 
 fn insert_sensitive_header(
@@ -71,6 +220,44 @@ fn insert_sensitive_header(
     headers.insert(header_name, header_value);
 }
 
+/// Percent-encodes a value to be used as a single path segment.
+pub(crate) fn encode_path_segment(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+async fn check_status(resp: Response) -> Result<Response> {
+    if resp.status().is_success() {
+        Ok(resp)
+    } else {
+        Err(ApiError::from_response(resp).await.into())
+    }
+}
+
+async fn decode_json<T: DeserializeOwned>(resp: Response) -> Result<T> {
+    let text = resp.text().await?;
+
+    // Some calls answer with an empty body instead of `null`.
+    let text = if text.trim().is_empty() { "null" } else { &text };
+
+    Ok(serde_json::from_str(text)?)
+}
+
+/// Returns a random number, good enough to spread retries over time or
+/// to pick query IDs, but not for anything security-related.
+pub(crate) fn random_u64() -> u64 {
+    // A freshly seeded hasher is a cheap source of randomness.
+    RandomState::new().build_hasher().finish()
+}
+
 fn now() -> u64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -81,11 +268,12 @@ fn now() -> u64 {
 // Public API
 
 pub struct OvhClient {
-    endpoint: &'static str,
-    application_key: String,
-    application_secret: String,
-    consumer_key: String,
+    endpoint: Endpoint,
+    auth: Auth,
     client: reqwest::Client,
+    time_delta: Mutex<Option<i64>>,
+    retry_policy: RetryPolicy,
+    limits: Limits,
 }
 
 impl OvhClient {
@@ -101,6 +289,9 @@ impl OvhClient {
     /// let client = OvhClient::new("ovh-eu", app_key, app_secret, consumer_key);
     /// assert!(client.is_some());
     ///
+    /// let client = OvhClient::new("http://127.0.0.1:8080/1.0", app_key, app_secret, consumer_key);
+    /// assert!(client.is_some());
+    ///
     /// let client = OvhClient::new("wrong-endpoint", app_key, app_secret, consumer_key);
     /// assert!(client.is_none());
     /// ```
@@ -110,27 +301,57 @@ impl OvhClient {
         application_secret: &str,
         consumer_key: &str,
     ) -> Option<OvhClient> {
-        let endpoint = ENDPOINTS.get(endpoint)?;
-        let application_key = application_key.into();
-        let application_secret = application_secret.into();
-        let consumer_key = consumer_key.into();
-
-        let client = reqwest::Client::new();
-
-        Some(OvhClient {
+        let endpoint = endpoint.parse().ok()?;
+        Some(Self::with_endpoint(
             endpoint,
             application_key,
             application_secret,
             consumer_key,
-            client,
-        })
+        ))
+    }
+
+    /// Creates a new client targeting the given endpoint.
+    ///
+    /// ```
+    /// use ovh::client::{Endpoint, OvhClient};
+    ///
+    /// let url = "http://127.0.0.1:8080/1.0".parse().unwrap();
+    /// let client = OvhClient::with_endpoint(
+    ///     Endpoint::Custom(url),
+    ///     "my_app_key",
+    ///     "my_app_secret",
+    ///     "my_consumer_key",
+    /// );
+    /// ```
+    pub fn with_endpoint(
+        endpoint: Endpoint,
+        application_key: &str,
+        application_secret: &str,
+        consumer_key: &str,
+    ) -> OvhClient {
+        OvhClient::builder()
+            .endpoint(endpoint)
+            .application_key(application_key)
+            .application_secret(application_secret)
+            .consumer_key(consumer_key)
+            .build()
+            // Only fails when the TLS backend cannot be initialized, in
+            // which case `reqwest::Client::new` would panic as well.
+            .expect("failed to create HTTP client")
+    }
+
+    /// Creates a builder to configure a new client.
+    pub fn builder() -> OvhClientBuilder {
+        OvhClientBuilder::new()
     }
 
     /// Creates a new client from a configuration file.
     ///
     /// The configuration file format is usually named `ovh.conf` and
     /// is the same format as the one used in the
-    /// [python-ovh](https://github.com/ovh/python-ovh) library:
+    /// [python-ovh](https://github.com/ovh/python-ovh) library.
+    /// The endpoint is either one of the known endpoint names or the
+    /// base URL of a custom endpoint:
     ///
     /// ```ini
     /// [default]
@@ -145,72 +366,134 @@ impl OvhClient {
     /// ; with a single consumer key.
     /// ;consumer_key=my_consumer_key
     /// ```
+    ///
+    /// OAuth2 service accounts are used instead of an application key
+    /// when the endpoint section contains `client_id` and `client_secret`
+    /// keys.
+    ///
+    /// The consumer key is optional, as long as only unauthenticated
+    /// calls are performed.
     pub fn from_conf<T>(path: T) -> Result<Self>
     where
         T: AsRef<Path>,
     {
-        let mut conf = Ini::new();
-        conf.load(path).map_err(|e| Error::ConfigError(e.to_string()))?;
-
-        let endpoint = conf
-            .get("default", "endpoint")
-            .ok_or(Error::ConfigError("missing key `endpoint`".to_string()) )?;
-
-        let application_key = conf
-            .get(&endpoint, "application_key")
-            .ok_or(Error::ConfigError("missing key `application_key`".to_string()))?;
-        let application_secret = conf
-            .get(&endpoint, "application_secret")
-            .ok_or(Error::ConfigError("missing key `application_secret`".to_string()))?;
-        let consumer_key = conf
-            .get(&endpoint, "consumer_key")
-            .ok_or(Error::ConfigError("missing key `consumer_key`".to_string()))?;
-
-        let c = Self::new(
-            &endpoint,
-            &application_key,
-            &application_secret,
-            &consumer_key,
-        )
-        .ok_or(Error::ConfigError("failed to create client".to_string()))?;
+        Self::from_config(&Config::from_file(path)?)
+    }
 
-        Ok(c)
+    /// Creates a new client from the environment and the usual
+    /// configuration files, like [python-ovh](https://github.com/ovh/python-ovh)
+    /// does.
+    ///
+    /// Each key is first looked up in the `OVH_ENDPOINT`,
+    /// `OVH_APPLICATION_KEY`, `OVH_APPLICATION_SECRET`, `OVH_CONSUMER_KEY`,
+    /// `OVH_CLIENT_ID` and `OVH_CLIENT_SECRET` environment variables, then
+    /// in the following
+    /// files, by decreasing priority:
+    ///
+    /// 1. `./ovh.conf`
+    /// 2. `$HOME/.ovh.conf`
+    /// 3. `/etc/ovh.conf`
+    ///
+    /// See [`OvhClient::from_conf`] for the format of these files, none
+    /// of which are required to exist.
+    pub fn from_env_or_conf() -> Result<Self> {
+        Self::from_config(&Config::discover()?)
     }
 
-    fn signature(&self, url: &str, timestamp: &str, method: &str, body: &str) -> String {
-        let values = [
-            &self.application_secret,
-            &self.consumer_key,
-            method,
-            url,
-            body,
-            timestamp,
-        ];
-        let sha = sha1::Sha1::from(values.join("+")).hexdigest();
-        format!("$1${}", sha)
+    fn from_config(conf: &Config) -> Result<Self> {
+        let endpoint = conf.require("default", "endpoint")?;
+        let mut builder = Self::builder().endpoint(endpoint.parse()?);
+
+        let client_id = conf.get(&endpoint, "client_id");
+        let client_secret = conf.get(&endpoint, "client_secret");
+
+        if client_id.is_some() || client_secret.is_some() {
+            if conf.get(&endpoint, "application_key").is_some() {
+                return Err(Error::ConfigError(
+                    "cannot use both `application_key` and `client_id`".to_string(),
+                ));
+            }
+
+            let client_id = conf.require(&endpoint, "client_id")?;
+            let client_secret = conf.require(&endpoint, "client_secret")?;
+            builder = builder.oauth2(&client_id, &client_secret);
+        } else {
+            let application_key = conf.require(&endpoint, "application_key")?;
+            let application_secret = conf.require(&endpoint, "application_secret")?;
+            builder = builder
+                .application_key(&application_key)
+                .application_secret(&application_secret);
+
+            if let Some(consumer_key) = conf.get(&endpoint, "consumer_key") {
+                builder = builder.consumer_key(&consumer_key);
+            }
+        }
+
+        builder.build()
+    }
+
+    /// Returns the endpoint targeted by the client.
+    pub fn endpoint(&self) -> &Endpoint {
+        &self.endpoint
     }
 
-    fn url(&self, path: &str) -> String {
-        format!("{}{}", &self.endpoint, path)
+    fn url(&self, version: ApiVersion, path: &str) -> Result<String> {
+        let base = self.endpoint.api_url(version).ok_or_else(|| {
+            Error::ConfigError(format!("no {:?} API for endpoint `{}`", version, self.endpoint))
+        })?;
+        Ok(format!("{}{}", base, path))
     }
 
     /// Retrieves the time delta between the local machine and the API server.
     ///
-    /// This method will perform a request to the API server to get its
-    /// local time, and then subtract it from the local time of the machine.
-    /// The result is a time delta value, is seconds.
+    /// The delta is fetched from the API server on first use, then
+    /// cached for the lifetime of the client. The result is a time delta
+    /// value in seconds, to be added to the local time to obtain the
+    /// server time.
     pub async fn time_delta(&self) -> Result<i64> {
-        let server_time: u64 = self.get_noauth("/auth/time").await?.text().await?.parse()?;
-        let delta = (now() - server_time).try_into()?;
+        // Keep the lock while fetching so that concurrent requests do
+        // not all hit `/auth/time` at once.
+        let mut cached = self.time_delta.lock().await;
+        if let Some(delta) = *cached {
+            return Ok(delta);
+        }
+
+        let delta = self.fetch_time_delta().await?;
+        *cached = Some(delta);
         Ok(delta)
     }
 
+    /// Fetches the time delta from the API server again and caches it.
+    ///
+    /// This is done automatically when the API rejects a request
+    /// because of an invalid timestamp.
+    pub async fn refresh_time_delta(&self) -> Result<i64> {
+        let delta = self.fetch_time_delta().await?;
+        *self.time_delta.lock().await = Some(delta);
+        Ok(delta)
+    }
+
+    async fn fetch_time_delta(&self) -> Result<i64> {
+        // Not using `get_noauth`, as signed requests depend on this call.
+        let resp = self
+            .client
+            .get(format!("{}/auth/time", self.endpoint.url()))
+            .headers(self.default_headers())
+            .send()
+            .await?;
+        let server_time: i64 = check_status(resp).await?.text().await?.trim().parse()?;
+        let now: i64 = now().try_into()?;
+        Ok(server_time - now)
+    }
+
     fn default_headers(&self) -> reqwest::header::HeaderMap {
         let mut headers = reqwest::header::HeaderMap::new();
-        headers.insert(
-            "X-Ovh-Application",
-            reqwest::header::HeaderValue::from_str(&self.application_key).unwrap(),
-        );
+        if let Some(application_key) = self.auth.application_key() {
+            headers.insert(
+                "X-Ovh-Application",
+                reqwest::header::HeaderValue::from_str(application_key).unwrap(),
+            );
+        }
         headers
     }
 
@@ -222,27 +505,82 @@ impl OvhClient {
     ) -> Result<HeaderMap> {
         let mut headers = self.default_headers();
 
+        let (application_secret, consumer_key) = match &self.auth {
+            Auth::ApplicationKey {
+                application_secret,
+                consumer_key,
+                ..
+            } => (application_secret, consumer_key),
+            Auth::OAuth2(oauth2) => {
+                let token = oauth2.access_token(&self.client).await?;
+                let value = format!("Bearer {}", token);
+                insert_sensitive_header(&mut headers, "Authorization", &value);
+                return Ok(headers);
+            }
+        };
+
+        let consumer_key = consumer_key
+            .as_deref()
+            .ok_or_else(|| Error::ConfigError("missing consumer key".to_string()))?;
+
         let time_delta = self.time_delta().await?;
         let now: i64 = now().try_into()?;
         let timestamp = now + time_delta;
         let timestamp = timestamp.to_string();
 
-        let signature = self.signature(url, &timestamp, method, body);
+        let signature = auth::signature(
+            application_secret,
+            consumer_key,
+            url,
+            &timestamp,
+            method,
+            body,
+        );
 
-        insert_sensitive_header(&mut headers, "X-Ovh-Consumer", &self.consumer_key);
+        insert_sensitive_header(&mut headers, "X-Ovh-Consumer", consumer_key);
         insert_sensitive_header(&mut headers, "X-Ovh-Timestamp", &timestamp);
         insert_sensitive_header(&mut headers, "X-Ovh-Signature", &signature);
 
         Ok(headers)
     }
 
+    /// Creates a request with an arbitrary method.
+    ///
+    /// All the other request methods are shortcuts for this one. The
+    /// request targets the `/1.0` API, unless changed with
+    /// [`RequestBuilder::api_version`].
+    pub fn request(&self, method: Method, path: &str) -> RequestBuilder<'_> {
+        RequestBuilder::new(self, method, path)
+    }
+
     /// Performs a GET request.
+    ///
+    /// Non-successful responses are turned into an [`Error::ApiError`].
+    /// Transient failures are retried according to the [`RetryPolicy`]
+    /// of the client.
     pub async fn get(&self, path: &str) -> Result<reqwest::Response> {
-        let url = self.url(path);
-        let headers = self.gen_headers(&url, "GET", "").await?;
+        self.request(Method::GET, path).send().await
+    }
 
-        let resp = self.client.get(url).headers(headers).send().await?;
-        Ok(resp)
+    /// Performs a GET request with query parameters.
+    ///
+    /// The parameters are percent-encoded before signing the request.
+    ///
+    /// ```no_run
+    /// # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+    /// let query = [("fieldType", "A"), ("subDomain", "www")];
+    /// let resp = client
+    ///     .get_with_query("/domain/zone/example.com/record", &query)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_with_query<Q: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        query: &Q,
+    ) -> Result<Response> {
+        self.request(Method::GET, path).query(query).send().await
     }
 
     /// Performs a DELETE request.
@@ -250,11 +588,7 @@ impl OvhClient {
         &self,
         path: &str,
     ) -> Result<reqwest::Response> {
-        let url = self.url(path);
-        let headers = self.gen_headers(&url, "DELETE", "").await?;
-
-        let resp = self.client.delete(url).headers(headers).send().await?;
-        Ok(resp)
+        self.request(Method::DELETE, path).send().await
     }
 
     /// Performs a POST request.
@@ -263,23 +597,7 @@ impl OvhClient {
         path: &str,
         data: &T,
     ) -> Result<Response> {
-        let url = self.url(path);
-
-        // Cannot call RequestBuilder.json directly because of body
-        // signature requirement.
-        let body = serde_json::to_string(data)?;
-        let mut headers = self.gen_headers(&url, "POST", &body).await?;
-
-        headers.insert("Content-type", "application/json".parse().unwrap());
-
-        let resp = self
-            .client
-            .post(url)
-            .headers(headers)
-            .body(body)
-            .send()
-            .await?;
-        Ok(resp)
+        self.request(Method::POST, path).json(data).send().await
     }
 
     /// Performs a PUT request.
@@ -288,23 +606,7 @@ impl OvhClient {
         path: &str,
         data: &T,
     ) -> Result<Response> {
-        let url = self.url(path);
-
-        // Cannot call RequestBuilder.json directly because of body
-        // signature requirement.
-        let body = serde_json::to_string(data)?;
-        let mut headers = self.gen_headers(&url, "PUT", &body).await?;
-
-        headers.insert("Content-type", "application/json".parse().unwrap());
-
-        let resp = self
-            .client
-            .post(url)
-            .headers(headers)
-            .body(body)
-            .send()
-            .await?;
-        Ok(resp)
+        self.request(Method::PUT, path).json(data).send().await
     }
 
     /// Performs a GET request without auth.
@@ -312,10 +614,87 @@ impl OvhClient {
         &self,
         path: &str,
     ) -> Result<reqwest::Response> {
-        let url = self.url(path);
-        let headers = self.default_headers();
+        self.request(Method::GET, path).signed(false).send().await
+    }
 
-        let resp = self.client.get(url).headers(headers).send().await?;
-        Ok(resp)
+    /// Performs a POST request without auth.
+    pub async fn post_noauth<T: Serialize + ?Sized>(
+        &self,
+        path: &str,
+        data: &T,
+    ) -> Result<Response> {
+        self.request(Method::POST, path)
+            .json(data)
+            .signed(false)
+            .send()
+            .await
+    }
+
+    /// Performs a GET request and deserializes the response body.
+    ///
+    /// ```no_run
+    /// # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+    /// let zones: Vec<String> = client.get_json("/domain/zone").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_json<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.request(Method::GET, path).send_json().await
+    }
+
+    /// Performs a GET request with query parameters and deserializes
+    /// the response body.
+    ///
+    /// ```no_run
+    /// # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+    /// use serde::Serialize;
+    ///
+    /// #[derive(Serialize)]
+    /// #[serde(rename_all = "camelCase")]
+    /// struct RecordFilter<'a> {
+    ///     field_type: &'a str,
+    ///     sub_domain: &'a str,
+    /// }
+    ///
+    /// let filter = RecordFilter { field_type: "A", sub_domain: "www" };
+    /// let ids: Vec<u64> = client
+    ///     .get_json_with_query("/domain/zone/example.com/record", &filter)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_json_with_query<Q, T>(&self, path: &str, query: &Q) -> Result<T>
+    where
+        Q: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        self.request(Method::GET, path).query(query).send_json().await
+    }
+
+    /// Performs a DELETE request and deserializes the response body.
+    ///
+    /// Use `()` as `T` for calls that do not return anything.
+    pub async fn delete_json<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        self.request(Method::DELETE, path).send_json().await
+    }
+
+    /// Performs a POST request and deserializes the response body.
+    pub async fn post_json<B, T>(&self, path: &str, data: &B) -> Result<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        self.request(Method::POST, path).json(data).send_json().await
+    }
+
+    /// Performs a PUT request and deserializes the response body.
+    ///
+    /// Use `()` as `T` for calls that do not return anything.
+    pub async fn put_json<B, T>(&self, path: &str, data: &B) -> Result<T>
+    where
+        B: Serialize + ?Sized,
+        T: DeserializeOwned,
+    {
+        self.request(Method::PUT, path).json(data).send_json().await
     }
 }
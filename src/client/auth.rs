@@ -0,0 +1,115 @@
+//! Authentication strategies.
+
+use super::{check_status, Result};
+use serde::Deserialize;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+/// How signed requests are authenticated.
+pub(super) enum Auth {
+    /// `$1$` signature built from an application key, its secret and a
+    /// consumer key.
+    ApplicationKey {
+        application_key: String,
+        application_secret: String,
+        consumer_key: Option<String>,
+    },
+    /// Bearer token of an OAuth2 service account.
+    OAuth2(OAuth2),
+}
+
+impl Auth {
+    pub(super) fn application_key(&self) -> Option<&str> {
+        match self {
+            Auth::ApplicationKey {
+                application_key, ..
+            } => Some(application_key),
+            Auth::OAuth2(_) => None,
+        }
+    }
+}
+
+pub(super) fn signature(
+    application_secret: &str,
+    consumer_key: &str,
+    url: &str,
+    timestamp: &str,
+    method: &str,
+    body: &str,
+) -> String {
+    let values = [
+        application_secret,
+        consumer_key,
+        method,
+        url,
+        body,
+        timestamp,
+    ];
+    let sha = sha1::Sha1::from(values.join("+")).hexdigest();
+    format!("$1${}", sha)
+}
+
+// Tokens are renewed a bit before their expiry so that they do not
+// expire while a request is in flight.
+const EXPIRY_MARGIN: Duration = Duration::from_secs(30);
+
+struct Token {
+    access_token: String,
+    expires_at: Instant,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    expires_in: u64,
+}
+
+/// OAuth2 client credentials flow, with token caching.
+pub(super) struct OAuth2 {
+    client_id: String,
+    client_secret: String,
+    token_url: String,
+    token: Mutex<Option<Token>>,
+}
+
+impl OAuth2 {
+    pub(super) fn new(client_id: String, client_secret: String, token_url: String) -> Self {
+        OAuth2 {
+            client_id,
+            client_secret,
+            token_url,
+            token: Mutex::new(None),
+        }
+    }
+
+    /// Returns the cached access token, fetching a new one if there is
+    /// none or if it is about to expire.
+    pub(super) async fn access_token(&self, client: &reqwest::Client) -> Result<String> {
+        let mut token = self.token.lock().await;
+        if let Some(token) = token.as_ref().filter(|t| Instant::now() < t.expires_at) {
+            return Ok(token.access_token.clone());
+        }
+
+        let params = [
+            ("grant_type", "client_credentials"),
+            ("client_id", &self.client_id),
+            ("client_secret", &self.client_secret),
+            ("scope", "all"),
+        ];
+        let resp = client.post(&self.token_url).form(&params).send().await?;
+        let resp: TokenResponse = check_status(resp).await?.json().await?;
+
+        let lifetime = Duration::from_secs(resp.expires_in).saturating_sub(EXPIRY_MARGIN);
+        *token = Some(Token {
+            access_token: resp.access_token.clone(),
+            expires_at: Instant::now() + lifetime,
+        });
+
+        Ok(resp.access_token)
+    }
+
+    /// Drops the cached access token, e.g. after it got revoked.
+    pub(super) async fn invalidate(&self) {
+        *self.token.lock().await = None;
+    }
+}
@@ -0,0 +1,157 @@
+//! Batch retrieval of objects through the `X-Ovh-Batch` header.
+
+use super::{encode_path_segment, Error, Method, OvhClient, Result};
+use serde::{de::DeserializeOwned, Deserialize};
+use std::{collections::HashMap, fmt::Display};
+
+// Keeps batch URLs well below the limits of the usual HTTP servers and
+// proxies.
+const MAX_BATCH_IDS_LEN: usize = 2000;
+
+#[derive(Deserialize)]
+struct BatchItem {
+    key: String,
+    value: Option<serde_json::Value>,
+    error: Option<String>,
+}
+
+/// Splits encoded IDs into comma-separated lists of bounded length.
+fn chunk_ids(ids: impl IntoIterator<Item = String>) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut chunk = String::new();
+
+    for id in ids {
+        if !chunk.is_empty() && chunk.len() + 1 + id.len() > MAX_BATCH_IDS_LEN {
+            chunks.push(std::mem::take(&mut chunk));
+        }
+        if !chunk.is_empty() {
+            chunk.push(',');
+        }
+        chunk.push_str(&id);
+    }
+
+    if !chunk.is_empty() {
+        chunks.push(chunk);
+    }
+    chunks
+}
+
+impl OvhClient {
+    /// Fetches many objects at once.
+    ///
+    /// `path_template` is the path of a single object, with `{}` in
+    /// place of its ID. The IDs are sent in as few requests as possible,
+    /// by comma-separating them in the path along with the
+    /// `X-Ovh-Batch: ,` header.
+    ///
+    /// Returns the object or the error reported by the API for each ID.
+    ///
+    /// ```no_run
+    /// # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+    /// let ids: Vec<u64> = client.get_json("/domain/zone/example.com/record").await?;
+    /// let records = client
+    ///     .get_batch::<serde_json::Value, _>("/domain/zone/example.com/record/{}", ids)
+    ///     .await?;
+    /// for (id, record) in records {
+    ///     match record {
+    ///         Ok(record) => println!("{}: {}", id, record),
+    ///         Err(e) => eprintln!("{}: {}", id, e),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_batch<T, I>(
+        &self,
+        path_template: &str,
+        ids: I,
+    ) -> Result<HashMap<String, std::result::Result<T, String>>>
+    where
+        T: DeserializeOwned,
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        if !path_template.contains("{}") {
+            return Err(Error::ArgumentError(format!(
+                "missing `{{}}` in batch path `{}`",
+                path_template
+            )));
+        }
+
+        let ids = ids
+            .into_iter()
+            .map(|id| encode_path_segment(&id.to_string()));
+
+        let mut results = HashMap::new();
+        for chunk in chunk_ids(ids) {
+            let path = path_template.replacen("{}", &chunk, 1);
+            let items: Vec<BatchItem> = self
+                .request(Method::GET, &path)
+                .header("X-Ovh-Batch", ",")
+                .send_json()
+                .await?;
+
+            for item in items {
+                let result = match item.error {
+                    Some(error) => Err(error),
+                    None => serde_json::from_value(item.value.unwrap_or_default())
+                        .map_err(|e| e.to_string()),
+                };
+                results.insert(item.key, result);
+            }
+        }
+
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks_stay_within_limit() {
+        let ids: Vec<String> = (0..1000).map(|i| format!("id-{:05}", i)).collect();
+        let chunks = chunk_ids(ids.clone());
+
+        assert!(chunks.len() > 1);
+        for chunk in &chunks {
+            assert!(chunk.len() <= MAX_BATCH_IDS_LEN);
+        }
+        let joined: Vec<_> = chunks.iter().flat_map(|c| c.split(',')).collect();
+        assert_eq!(joined, ids);
+    }
+
+    #[test]
+    fn fills_chunks_up_to_limit() {
+        // Each ID takes 10 bytes with its separator, the last one 9.
+        let ids: Vec<String> = (0..201).map(|i| format!("{:09}", i)).collect();
+        let chunks = chunk_ids(ids);
+
+        assert_eq!(chunks.len(), 2);
+        assert_eq!(chunks[0].len(), 200 * 10 - 1);
+        assert_eq!(chunks[1], "000000200");
+    }
+
+    #[test]
+    fn sends_oversized_ids_alone() {
+        let long = "x".repeat(MAX_BATCH_IDS_LEN + 1);
+        let chunks = chunk_ids(vec!["a".to_string(), long.clone(), "b".to_string()]);
+
+        assert_eq!(chunks, vec!["a".to_string(), long, "b".to_string()]);
+    }
+
+    #[test]
+    fn no_ids_no_chunks() {
+        assert!(chunk_ids(Vec::new()).is_empty());
+    }
+
+    #[tokio::test]
+    async fn requires_id_placeholder() {
+        let client = OvhClient::new("http://127.0.0.1:1/1.0", "key", "secret", "consumer").unwrap();
+        let result = client
+            .get_batch::<serde_json::Value, _>("/domain/zone/example.com/record", &[1, 2])
+            .await;
+        assert!(matches!(result, Err(Error::ArgumentError(_))));
+    }
+}
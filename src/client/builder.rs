@@ -0,0 +1,283 @@
+//! Configurable construction of [`OvhClient`].
+
+use super::{
+    auth::{Auth, OAuth2},
+    limit::Limits,
+    Endpoint, Error, OvhClient, Result, RetryPolicy,
+};
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+const DEFAULT_USER_AGENT: &str = concat!("rust-ovh/", env!("CARGO_PKG_VERSION"));
+
+/// Builder for [`OvhClient`].
+///
+/// ```
+/// use ovh::client::{Endpoint, OvhClient};
+/// use std::time::Duration;
+///
+/// let client = OvhClient::builder()
+///     .endpoint(Endpoint::OvhEu)
+///     .application_key("my_app_key")
+///     .application_secret("my_app_secret")
+///     .consumer_key("my_consumer_key")
+///     .connect_timeout(Duration::from_secs(5))
+///     .timeout(Duration::from_secs(30))
+///     .user_agent("my-automation/1.0")
+///     .build();
+/// assert!(client.is_ok());
+/// ```
+#[derive(Debug, Default)]
+pub struct OvhClientBuilder {
+    endpoint: Option<Endpoint>,
+    application_key: Option<String>,
+    application_secret: Option<String>,
+    consumer_key: Option<String>,
+    client_id: Option<String>,
+    client_secret: Option<String>,
+    oauth2_token_url: Option<String>,
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    user_agent: Option<String>,
+    proxies: Vec<reqwest::Proxy>,
+    no_proxy: bool,
+    pool_idle_timeout: Option<Option<Duration>>,
+    pool_max_idle_per_host: Option<usize>,
+    http_client: Option<reqwest::Client>,
+    retry_policy: Option<RetryPolicy>,
+    rate_limit: Option<(u32, Duration)>,
+    max_in_flight: Option<usize>,
+}
+
+impl OvhClientBuilder {
+    /// Creates a builder with default settings.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the API endpoint. This is mandatory.
+    pub fn endpoint(mut self, endpoint: Endpoint) -> Self {
+        self.endpoint = Some(endpoint);
+        self
+    }
+
+    /// Sets the application key.
+    ///
+    /// This is mandatory unless authenticating with OAuth2.
+    pub fn application_key(mut self, application_key: &str) -> Self {
+        self.application_key = Some(application_key.into());
+        self
+    }
+
+    /// Sets the application secret.
+    ///
+    /// This is mandatory unless authenticating with OAuth2.
+    pub fn application_secret(mut self, application_secret: &str) -> Self {
+        self.application_secret = Some(application_secret.into());
+        self
+    }
+
+    /// Sets the consumer key.
+    ///
+    /// This is only mandatory for authenticated calls.
+    pub fn consumer_key(mut self, consumer_key: &str) -> Self {
+        self.consumer_key = Some(consumer_key.into());
+        self
+    }
+
+    /// Authenticates with the OAuth2 client credentials of a service
+    /// account instead of an application key.
+    ///
+    /// Access tokens are fetched on first use and renewed when they
+    /// expire.
+    pub fn oauth2(mut self, client_id: &str, client_secret: &str) -> Self {
+        self.client_id = Some(client_id.into());
+        self.client_secret = Some(client_secret.into());
+        self
+    }
+
+    /// Sets the URL of the OAuth2 token endpoint.
+    ///
+    /// This is only needed for endpoints without a known token endpoint,
+    /// see [`Endpoint::oauth2_token_url`].
+    pub fn oauth2_token_url(mut self, url: &str) -> Self {
+        self.oauth2_token_url = Some(url.into());
+        self
+    }
+
+    /// Sets the timeout for establishing connections to the API.
+    pub fn connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the total timeout of every request, from connection to the
+    /// end of the response body.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the `User-Agent` header sent with every request.
+    ///
+    /// Defaults to `rust-ovh/<version>`.
+    pub fn user_agent(mut self, user_agent: &str) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Adds a proxy to route requests through.
+    ///
+    /// Without any proxy, the system proxies defined by the usual
+    /// environment variables are used.
+    pub fn proxy(mut self, proxy: reqwest::Proxy) -> Self {
+        self.proxies.push(proxy);
+        self
+    }
+
+    /// Disables every proxy, including system ones.
+    pub fn no_proxy(mut self) -> Self {
+        self.no_proxy = true;
+        self
+    }
+
+    /// Sets how long idle connections are kept in the pool, `None`
+    /// meaning forever.
+    pub fn pool_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Sets the maximum number of idle connections kept per host.
+    pub fn pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Uses a preconfigured HTTP client.
+    ///
+    /// The timeout, user agent, proxy and pool settings of this builder
+    /// are then ignored, the ones of the given client being used instead.
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.http_client = Some(client);
+        self
+    }
+
+    /// Sets the policy for retrying requests failing because of
+    /// transient errors.
+    ///
+    /// Defaults to [`RetryPolicy::default`]; use [`RetryPolicy::none`]
+    /// to disable retries.
+    pub fn retry_policy(mut self, policy: RetryPolicy) -> Self {
+        self.retry_policy = Some(policy);
+        self
+    }
+
+    /// Limits the client to `max_requests` requests per `period`.
+    ///
+    /// Requests beyond this rate are delayed until they fit in, with
+    /// bursts of up to `max_requests` requests allowed.
+    ///
+    /// ```
+    /// use ovh::client::{Endpoint, OvhClient};
+    /// use std::time::Duration;
+    ///
+    /// let client = OvhClient::builder()
+    ///     .endpoint(Endpoint::OvhEu)
+    ///     .application_key("my_app_key")
+    ///     .application_secret("my_app_secret")
+    ///     .rate_limit(20, Duration::from_secs(1))
+    ///     .max_in_flight(8)
+    ///     .build();
+    /// assert!(client.is_ok());
+    /// ```
+    pub fn rate_limit(mut self, max_requests: u32, period: Duration) -> Self {
+        self.rate_limit = Some((max_requests, period));
+        self
+    }
+
+    /// Limits the number of requests sent concurrently by the client.
+    pub fn max_in_flight(mut self, max: usize) -> Self {
+        self.max_in_flight = Some(max);
+        self
+    }
+
+    /// Creates the client.
+    ///
+    /// Fails if a mandatory setting is missing or if the HTTP client
+    /// cannot be created.
+    pub fn build(self) -> Result<OvhClient> {
+        let endpoint = self
+            .endpoint
+            .ok_or_else(|| Error::ConfigError("missing endpoint".to_string()))?;
+
+        let auth = if self.client_id.is_some() || self.client_secret.is_some() {
+            if self.application_key.is_some() {
+                return Err(Error::ConfigError(
+                    "cannot use both an application key and OAuth2".to_string(),
+                ));
+            }
+
+            let client_id = self
+                .client_id
+                .ok_or_else(|| Error::ConfigError("missing OAuth2 client ID".to_string()))?;
+            let client_secret = self
+                .client_secret
+                .ok_or_else(|| Error::ConfigError("missing OAuth2 client secret".to_string()))?;
+            let token_url = self
+                .oauth2_token_url
+                .or_else(|| endpoint.oauth2_token_url().map(String::from))
+                .ok_or_else(|| Error::ConfigError("missing OAuth2 token URL".to_string()))?;
+
+            Auth::OAuth2(OAuth2::new(client_id, client_secret, token_url))
+        } else {
+            Auth::ApplicationKey {
+                application_key: self
+                    .application_key
+                    .ok_or_else(|| Error::ConfigError("missing application key".to_string()))?,
+                application_secret: self
+                    .application_secret
+                    .ok_or_else(|| Error::ConfigError("missing application secret".to_string()))?,
+                consumer_key: self.consumer_key,
+            }
+        };
+
+        let client = match self.http_client {
+            Some(client) => client,
+            None => {
+                let user_agent = self.user_agent.as_deref().unwrap_or(DEFAULT_USER_AGENT);
+                let mut builder = reqwest::Client::builder().user_agent(user_agent);
+
+                if let Some(timeout) = self.connect_timeout {
+                    builder = builder.connect_timeout(timeout);
+                }
+                if let Some(timeout) = self.timeout {
+                    builder = builder.timeout(timeout);
+                }
+                if self.no_proxy {
+                    builder = builder.no_proxy();
+                }
+                for proxy in self.proxies {
+                    builder = builder.proxy(proxy);
+                }
+                if let Some(timeout) = self.pool_idle_timeout {
+                    builder = builder.pool_idle_timeout(timeout);
+                }
+                if let Some(max) = self.pool_max_idle_per_host {
+                    builder = builder.pool_max_idle_per_host(max);
+                }
+
+                builder.build()?
+            }
+        };
+
+        Ok(OvhClient {
+            endpoint,
+            auth,
+            client,
+            time_delta: Mutex::new(None),
+            retry_policy: self.retry_policy.unwrap_or_default(),
+            limits: Limits::new(self.rate_limit, self.max_in_flight),
+        })
+    }
+}
@@ -0,0 +1,71 @@
+//! Configuration lookup, compatible with
+//! [python-ovh](https://github.com/ovh/python-ovh).
+
+use super::{Error, Result};
+use configparser::ini::Ini;
+use std::{env, path::Path, path::PathBuf};
+
+/// Configuration sources, by decreasing priority.
+pub(super) struct Config {
+    use_env: bool,
+    files: Vec<Ini>,
+}
+
+fn load_file<T: AsRef<Path>>(path: T) -> Result<Ini> {
+    let mut ini = Ini::new();
+    ini.load(path).map_err(Error::ConfigError)?;
+    Ok(ini)
+}
+
+/// Paths of the configuration files, by decreasing priority.
+fn default_paths() -> Vec<PathBuf> {
+    let mut paths = vec![PathBuf::from("./ovh.conf")];
+    if let Some(home) = env::var_os("HOME") {
+        paths.push(Path::new(&home).join(".ovh.conf"));
+    }
+    paths.push(PathBuf::from("/etc/ovh.conf"));
+    paths
+}
+
+impl Config {
+    /// Uses a single configuration file.
+    pub(super) fn from_file<T: AsRef<Path>>(path: T) -> Result<Self> {
+        Ok(Config {
+            use_env: false,
+            files: vec![load_file(path)?],
+        })
+    }
+
+    /// Uses the `OVH_*` environment variables, then `./ovh.conf`,
+    /// `$HOME/.ovh.conf` and `/etc/ovh.conf`, skipping missing files.
+    pub(super) fn discover() -> Result<Self> {
+        let files = default_paths()
+            .into_iter()
+            .filter(|path| path.is_file())
+            .map(load_file)
+            .collect::<Result<_>>()?;
+
+        Ok(Config {
+            use_env: true,
+            files,
+        })
+    }
+
+    /// Looks a key up, the `OVH_<KEY>` environment variable taking
+    /// precedence over the files when enabled.
+    pub(super) fn get(&self, section: &str, key: &str) -> Option<String> {
+        if self.use_env {
+            let var = format!("OVH_{}", key.to_uppercase());
+            if let Some(value) = env::var(var).ok().filter(|v| !v.is_empty()) {
+                return Some(value);
+            }
+        }
+
+        self.files.iter().find_map(|ini| ini.get(section, key))
+    }
+
+    pub(super) fn require(&self, section: &str, key: &str) -> Result<String> {
+        self.get(section, key)
+            .ok_or_else(|| Error::ConfigError(format!("missing key `{}`", key)))
+    }
+}
@@ -0,0 +1,217 @@
+//! Creation of consumer keys through `/auth/credential`.
+
+use super::{auth::Auth, Error, OvhClient, Result};
+use serde::{Deserialize, Serialize};
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+/// HTTP method and path pattern granted to a consumer key.
+///
+/// Paths may contain `*` wildcards, e.g. `/domain/zone/*`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessRule {
+    pub method: String,
+    pub path: String,
+}
+
+impl AccessRule {
+    /// Creates a rule for an arbitrary method.
+    pub fn new(method: &str, path: &str) -> Self {
+        AccessRule {
+            method: method.to_uppercase(),
+            path: path.into(),
+        }
+    }
+
+    /// Grants GET calls on `path`.
+    pub fn get(path: &str) -> Self {
+        Self::new("GET", path)
+    }
+
+    /// Grants POST calls on `path`.
+    pub fn post(path: &str) -> Self {
+        Self::new("POST", path)
+    }
+
+    /// Grants PUT calls on `path`.
+    pub fn put(path: &str) -> Self {
+        Self::new("PUT", path)
+    }
+
+    /// Grants DELETE calls on `path`.
+    pub fn delete(path: &str) -> Self {
+        Self::new("DELETE", path)
+    }
+}
+
+/// Request for a new consumer key.
+///
+/// ```
+/// use ovh::client::{AccessRule, CredentialRequest};
+///
+/// let request = CredentialRequest::new()
+///     .rule(AccessRule::get("/domain/*"))
+///     .rule(AccessRule::post("/domain/zone/*"))
+///     .redirection("https://example.com/validated");
+/// ```
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CredentialRequest {
+    access_rules: Vec<AccessRule>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    redirection: Option<String>,
+}
+
+impl CredentialRequest {
+    /// Creates a request without any access rule.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an access rule.
+    pub fn rule(mut self, rule: AccessRule) -> Self {
+        self.access_rules.push(rule);
+        self
+    }
+
+    /// Sets the URL the user is redirected to once the key is validated.
+    pub fn redirection(mut self, url: &str) -> Self {
+        self.redirection = Some(url.into());
+        self
+    }
+}
+
+/// Validation state of a consumer key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum CredentialState {
+    Expired,
+    PendingValidation,
+    Refused,
+    Validated,
+}
+
+/// Consumer key waiting for the user to validate it.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PendingCredential {
+    /// URL the user must visit to validate the key.
+    pub validation_url: String,
+    pub consumer_key: String,
+    pub state: CredentialState,
+}
+
+/// Consumer key, as seen by `/auth/currentCredential`.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Credential {
+    pub credential_id: u64,
+    pub application_id: u64,
+    pub creation: String,
+    pub expiration: Option<String>,
+    pub last_use: Option<String>,
+    pub status: CredentialState,
+    pub rules: Vec<AccessRule>,
+}
+
+impl OvhClient {
+    /// Requests a new consumer key granting the given access rules.
+    ///
+    /// The returned key is unusable until the user visits its
+    /// validation URL; see [`OvhClient::wait_for_credential`].
+    pub async fn request_credential(
+        &self,
+        request: &CredentialRequest,
+    ) -> Result<PendingCredential> {
+        let resp = self.post_noauth("/auth/credential", request).await?;
+        Ok(resp.json().await?)
+    }
+
+    /// Retrieves the consumer key used by the client.
+    pub async fn current_credential(&self) -> Result<Credential> {
+        self.get_json("/auth/currentCredential").await
+    }
+
+    /// Waits for a consumer key to be validated, then returns a client
+    /// using it.
+    ///
+    /// The key is checked every `poll_interval`, for at most `timeout`.
+    ///
+    /// [`Error::CredentialError`] is only returned when
+    /// `/auth/currentCredential` answers with the key in another state than
+    /// `validated` or `pendingValidation`, e.g. `expired`. The API mostly
+    /// rejects refused and expired keys with `INVALID_CREDENTIAL`, just like
+    /// keys waiting for validation, so a key the user refuses is usually
+    /// only reported once the wait times out, as a [`Error::TimeoutError`].
+    /// A `timeout` too large to be represented, such as [`Duration::MAX`],
+    /// waits forever.
+    ///
+    /// ```no_run
+    /// # async fn example() -> ovh::client::Result<()> {
+    /// use ovh::client::{AccessRule, CredentialRequest, OvhClient};
+    /// use std::time::Duration;
+    ///
+    /// let client = OvhClient::from_env_or_conf()?;
+    /// let request = CredentialRequest::new().rule(AccessRule::get("/*"));
+    /// let pending = client.request_credential(&request).await?;
+    /// println!("Please visit {}", pending.validation_url);
+    ///
+    /// let client = client
+    ///     .wait_for_credential(
+    ///         &pending.consumer_key,
+    ///         Duration::from_secs(5),
+    ///         Duration::from_secs(600),
+    ///     )
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_for_credential(
+        mut self,
+        consumer_key: &str,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<OvhClient> {
+        match &mut self.auth {
+            Auth::ApplicationKey {
+                consumer_key: key, ..
+            } => *key = Some(consumer_key.into()),
+            Auth::OAuth2(_) => {
+                return Err(Error::ConfigError(
+                    "consumer keys require an application key".to_string(),
+                ))
+            }
+        }
+
+        let deadline = Instant::now().checked_add(timeout);
+        loop {
+            let status = match self.current_credential().await {
+                Ok(credential) => credential.status,
+                // Keys waiting for validation are not considered valid
+                // credentials yet.
+                Err(Error::ApiError(e)) if e.is_invalid_credential() => {
+                    CredentialState::PendingValidation
+                }
+                Err(e) => return Err(e),
+            };
+
+            match status {
+                CredentialState::Validated => return Ok(self),
+                CredentialState::PendingValidation => {}
+                state => return Err(Error::CredentialError(state)),
+            }
+
+            let remaining = match deadline {
+                Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+                None => Duration::MAX,
+            };
+            if remaining.is_zero() {
+                return Err(Error::TimeoutError(format!(
+                    "consumer key not validated after {:?}",
+                    timeout
+                )));
+            }
+            sleep(poll_interval.min(remaining)).await;
+        }
+    }
+}
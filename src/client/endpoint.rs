@@ -0,0 +1,172 @@
+//! OVH API endpoints.
+
+use super::Error;
+use reqwest::Url;
+use std::{fmt, str::FromStr};
+
+/// Version of the OVH API.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ApiVersion {
+    /// The historical `/1.0` API.
+    #[default]
+    V1,
+    /// The `/v2` API.
+    V2,
+}
+
+impl ApiVersion {
+    /// Returns the path prefix of the version, e.g. `/1.0`.
+    pub fn prefix(self) -> &'static str {
+        match self {
+            ApiVersion::V1 => "/1.0",
+            ApiVersion::V2 => "/v2",
+        }
+    }
+}
+
+/// An OVH API endpoint.
+///
+/// Known endpoints can be parsed from their usual name, as used in
+/// `ovh.conf`; any other string is parsed as the URL of a custom
+/// endpoint.
+///
+/// Custom URLs are the base URL of the `/1.0` API, used as given. The
+/// `/v2` API is only available when they end with `/1.0`, by replacing
+/// that suffix.
+///
+/// ```
+/// use ovh::client::{ApiVersion, Endpoint};
+///
+/// let endpoint: Endpoint = "ovh-eu".parse().unwrap();
+/// assert_eq!(endpoint, Endpoint::OvhEu);
+/// assert_eq!(endpoint.url(), "https://eu.api.ovh.com/1.0");
+/// assert_eq!(
+///     endpoint.api_url(ApiVersion::V2).as_deref(),
+///     Some("https://eu.api.ovh.com/v2")
+/// );
+///
+/// let endpoint: Endpoint = "http://127.0.0.1:8080/1.0".parse().unwrap();
+/// assert_eq!(endpoint.url(), "http://127.0.0.1:8080/1.0");
+/// assert_eq!(
+///     endpoint.api_url(ApiVersion::V2).as_deref(),
+///     Some("http://127.0.0.1:8080/v2")
+/// );
+///
+/// let endpoint: Endpoint = "https://proxy.example.com/ovh".parse().unwrap();
+/// assert_eq!(endpoint.url(), "https://proxy.example.com/ovh");
+/// assert_eq!(endpoint.api_url(ApiVersion::V2), None);
+///
+/// assert!("wrong-endpoint".parse::<Endpoint>().is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Endpoint {
+    OvhEu,
+    OvhUs,
+    OvhCa,
+    KimsufiEu,
+    KimsufiCa,
+    SoyoustartEu,
+    SoyoustartCa,
+    /// Any other base URL, e.g. a local stand-in or a proxy.
+    Custom(Url),
+}
+
+static ENDPOINTS: phf::Map<&'static str, Endpoint> = phf::phf_map! {
+    "ovh-eu" => Endpoint::OvhEu,
+    "ovh-us" => Endpoint::OvhUs,
+    "ovh-ca" => Endpoint::OvhCa,
+    "kimsufi-eu" => Endpoint::KimsufiEu,
+    "kimsufi-ca" => Endpoint::KimsufiCa,
+    "soyoustart-eu" => Endpoint::SoyoustartEu,
+    "soyoustart-ca" => Endpoint::SoyoustartCa,
+};
+
+impl Endpoint {
+    /// Returns the usual name of the endpoint, or `None` for custom ones.
+    pub fn name(&self) -> Option<&'static str> {
+        let name = match self {
+            Endpoint::OvhEu => "ovh-eu",
+            Endpoint::OvhUs => "ovh-us",
+            Endpoint::OvhCa => "ovh-ca",
+            Endpoint::KimsufiEu => "kimsufi-eu",
+            Endpoint::KimsufiCa => "kimsufi-ca",
+            Endpoint::SoyoustartEu => "soyoustart-eu",
+            Endpoint::SoyoustartCa => "soyoustart-ca",
+            Endpoint::Custom(_) => return None,
+        };
+        Some(name)
+    }
+
+    /// Returns the base URL of the given API version, or `None` for the
+    /// `/v2` API of custom URLs not ending with `/1.0`.
+    pub fn api_url(&self, version: ApiVersion) -> Option<String> {
+        let root = match self {
+            Endpoint::OvhEu => "https://eu.api.ovh.com",
+            Endpoint::OvhUs => "https://api.us.ovhcloud.com",
+            Endpoint::OvhCa => "https://ca.api.ovh.com",
+            Endpoint::KimsufiEu => "https://eu.api.kimsufi.com",
+            Endpoint::KimsufiCa => "https://ca.api.kimsufi.com",
+            Endpoint::SoyoustartEu => "https://eu.api.soyoustart.com",
+            Endpoint::SoyoustartCa => "https://ca.api.soyoustart.com",
+            Endpoint::Custom(url) => {
+                let url = url.as_str().trim_end_matches('/');
+                return match version {
+                    ApiVersion::V1 => Some(url.to_string()),
+                    ApiVersion::V2 => url
+                        .strip_suffix(ApiVersion::V1.prefix())
+                        .map(|root| format!("{}{}", root, ApiVersion::V2.prefix())),
+                };
+            }
+        };
+        Some(format!("{}{}", root, version.prefix()))
+    }
+
+    /// Returns the base URL of the `/1.0` API.
+    pub fn url(&self) -> String {
+        // Every endpoint has a `/1.0` API.
+        self.api_url(ApiVersion::V1).unwrap()
+    }
+
+    /// Returns the URL of the OAuth2 token endpoint, if the endpoint
+    /// supports service accounts.
+    pub fn oauth2_token_url(&self) -> Option<&'static str> {
+        match self {
+            Endpoint::OvhEu => Some("https://www.ovh.com/auth/oauth2/token"),
+            Endpoint::OvhUs => Some("https://us.ovhcloud.com/auth/oauth2/token"),
+            Endpoint::OvhCa => Some("https://ca.ovh.com/auth/oauth2/token"),
+            _ => None,
+        }
+    }
+}
+
+impl FromStr for Endpoint {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some(endpoint) = ENDPOINTS.get(s) {
+            return Ok(endpoint.clone());
+        }
+
+        match Url::parse(s) {
+            Ok(url) if url.scheme() == "http" || url.scheme() == "https" => {
+                Ok(Endpoint::Custom(url))
+            }
+            _ => Err(Error::ConfigError(format!("unknown endpoint `{}`", s))),
+        }
+    }
+}
+
+impl From<Url> for Endpoint {
+    fn from(url: Url) -> Self {
+        Endpoint::Custom(url)
+    }
+}
+
+impl fmt::Display for Endpoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.name() {
+            Some(name) => f.write_str(name),
+            None => f.write_str(&self.url()),
+        }
+    }
+}
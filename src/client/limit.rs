@@ -0,0 +1,143 @@
+//! Client-side pacing of requests.
+
+use std::time::Duration;
+use tokio::{
+    sync::{Mutex, Semaphore, SemaphorePermit},
+    time::Instant,
+};
+
+struct Bucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+/// Token bucket allowing bursts of up to `capacity` requests, refilled
+/// at `rate` requests per second.
+struct RateLimiter {
+    capacity: f64,
+    rate: f64,
+    bucket: Mutex<Bucket>,
+}
+
+impl RateLimiter {
+    fn new(max_requests: u32, period: Duration) -> Self {
+        let capacity = f64::from(max_requests.max(1));
+        RateLimiter {
+            capacity,
+            rate: capacity / period.as_secs_f64(),
+            bucket: Mutex::new(Bucket {
+                tokens: capacity,
+                last_refill: Instant::now(),
+            }),
+        }
+    }
+
+    async fn acquire(&self) {
+        let wait = {
+            let mut bucket = self.bucket.lock().await;
+
+            let now = Instant::now();
+            let elapsed = now.duration_since(bucket.last_refill).as_secs_f64();
+            bucket.tokens = (bucket.tokens + elapsed * self.rate).min(self.capacity);
+            bucket.last_refill = now;
+
+            // Reserve a token even when the bucket is empty, so that
+            // waiting callers are served in order.
+            bucket.tokens -= 1.0;
+            if bucket.tokens >= 0.0 {
+                return;
+            }
+            Duration::from_secs_f64(-bucket.tokens / self.rate)
+        };
+
+        tokio::time::sleep(wait).await;
+    }
+}
+
+/// Limits shared by every request of a client.
+pub(super) struct Limits {
+    rate: Option<RateLimiter>,
+    in_flight: Option<Semaphore>,
+}
+
+impl Limits {
+    pub(super) fn new(rate_limit: Option<(u32, Duration)>, max_in_flight: Option<usize>) -> Self {
+        Limits {
+            rate: rate_limit.map(|(max_requests, period)| RateLimiter::new(max_requests, period)),
+            in_flight: max_in_flight.map(|max| Semaphore::new(max.max(1))),
+        }
+    }
+
+    /// Waits until a request may be sent. The request is considered in
+    /// flight until the returned permit is dropped.
+    pub(super) async fn acquire(&self) -> Option<SemaphorePermit<'_>> {
+        let permit = match &self.in_flight {
+            // The semaphore is never closed.
+            Some(semaphore) => Some(semaphore.acquire().await.unwrap()),
+            None => None,
+        };
+
+        if let Some(rate) = &self.rate {
+            rate.acquire().await;
+        }
+
+        permit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test(start_paused = true)]
+    async fn bursts_then_paces() {
+        let limiter = RateLimiter::new(3, Duration::from_secs(1));
+        let start = Instant::now();
+
+        for _ in 0..3 {
+            limiter.acquire().await;
+        }
+        assert_eq!(start.elapsed(), Duration::ZERO);
+
+        for i in 1..=3 {
+            limiter.acquire().await;
+            let expected = Duration::from_millis(333 * i);
+            let elapsed = start.elapsed();
+            assert!(
+                elapsed >= expected && elapsed <= expected + Duration::from_millis(5),
+                "request {}: {:?}",
+                i,
+                elapsed
+            );
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn refills_while_idle() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(1));
+        limiter.acquire().await;
+        limiter.acquire().await;
+
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        let start = Instant::now();
+        limiter.acquire().await;
+        limiter.acquire().await;
+        // The bucket does not hold more than its capacity.
+        assert_eq!(start.elapsed(), Duration::ZERO);
+        limiter.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(500));
+    }
+
+    #[tokio::test]
+    async fn limits_in_flight_requests() {
+        let limits = Limits::new(None, Some(2));
+        let first = limits.acquire().await;
+        let _second = limits.acquire().await;
+
+        let third = tokio::time::timeout(Duration::from_millis(50), limits.acquire()).await;
+        assert!(third.is_err());
+
+        drop(first);
+        assert!(limits.acquire().await.is_some());
+    }
+}
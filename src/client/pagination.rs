@@ -0,0 +1,349 @@
+//! Listing of objects, either paginated through the `X-Pagination-*`
+//! headers or fetched one by one.
+
+use super::{decode_json, ApiVersion, Method, OvhClient, RequestBuilder, Result};
+use futures::{stream, Stream, TryStreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+
+enum Cursor {
+    First,
+    Next(String),
+    Done,
+}
+
+/// Fetches the page at `cursor`, returning it along with the cursor of
+/// the next page.
+async fn fetch_page<T: DeserializeOwned>(
+    req: RequestBuilder<'_>,
+    cursor: Cursor,
+) -> Result<Option<(Vec<T>, Cursor)>> {
+    let req = match cursor {
+        Cursor::First => req,
+        Cursor::Next(cursor) => req.header("X-Pagination-Cursor", &cursor),
+        Cursor::Done => return Ok(None),
+    };
+
+    let resp = req.send().await?;
+    // An empty or unreadable cursor would request the first page again.
+    let next = match resp
+        .headers()
+        .get("X-Pagination-Cursor-Next")
+        .and_then(|cursor| cursor.to_str().ok())
+    {
+        Some(cursor) if !cursor.is_empty() => Cursor::Next(cursor.to_string()),
+        _ => Cursor::Done,
+    };
+    let page = decode_json(resp).await?;
+
+    Ok(Some((page, next)))
+}
+
+impl OvhClient {
+    /// Lists full objects page by page.
+    ///
+    /// Instead of the usual list of IDs, `path` is requested with the
+    /// `X-Pagination-Mode: CachedObjectList-Pages` header, which makes
+    /// the API return the objects themselves, `page_size` at a time.
+    /// Pages are only fetched as the stream is consumed.
+    ///
+    /// ```no_run
+    /// # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+    /// use futures::TryStreamExt;
+    ///
+    /// let mut domains = Box::pin(client.paginate::<serde_json::Value>("/domain", 100));
+    /// while let Some(domain) = domains.try_next().await? {
+    ///     println!("{}", domain["domain"]);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn paginate<'a, T>(
+        &'a self,
+        path: &str,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<T>> + 'a
+    where
+        T: DeserializeOwned + 'a,
+    {
+        self.paginate_version(ApiVersion::V1, path, page_size)
+    }
+
+    /// Lists objects of the `/v2` API page by page.
+    ///
+    /// The `/v2` API always returns full objects, `page_size` at a time,
+    /// the following pages being requested through the
+    /// `X-Pagination-Cursor` header. Pages are only fetched as the
+    /// stream is consumed.
+    ///
+    /// ```no_run
+    /// # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+    /// use futures::TryStreamExt;
+    ///
+    /// let policies: Vec<serde_json::Value> = client
+    ///     .paginate_v2("/iam/policy", 50)
+    ///     .try_collect()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn paginate_v2<'a, T>(
+        &'a self,
+        path: &str,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<T>> + 'a
+    where
+        T: DeserializeOwned + 'a,
+    {
+        self.paginate_version(ApiVersion::V2, path, page_size)
+    }
+
+    fn paginate_version<'a, T>(
+        &'a self,
+        version: ApiVersion,
+        path: &str,
+        page_size: u32,
+    ) -> impl Stream<Item = Result<T>> + 'a
+    where
+        T: DeserializeOwned + 'a,
+    {
+        let path = path.to_string();
+        let page_size = page_size.to_string();
+
+        let pages = stream::try_unfold(Cursor::First, move |cursor| {
+            let mut req = self
+                .request(Method::GET, &path)
+                .api_version(version)
+                .header("X-Pagination-Size", &page_size);
+            // Only needed by the `/1.0` API, which returns IDs otherwise.
+            if version == ApiVersion::V1 {
+                req = req.header("X-Pagination-Mode", "CachedObjectList-Pages");
+            }
+            fetch_page(req, cursor)
+        });
+
+        pages
+            .map_ok(|page: Vec<T>| stream::iter(page.into_iter().map(Ok)))
+            .try_flatten()
+    }
+
+    /// Lists IDs, then fetches the corresponding objects.
+    ///
+    /// This is meant for list endpoints that do not support
+    /// [`OvhClient::paginate`]: `list_path` is expected to return a list
+    /// of IDs, and `item_path` gives the path of the object for each of
+    /// them. At most `concurrency` objects are fetched at once, and they
+    /// are yielded in the order of the list.
+    ///
+    /// ```no_run
+    /// # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+    /// use futures::TryStreamExt;
+    ///
+    /// let records: Vec<serde_json::Value> = client
+    ///     .list_and_fetch(
+    ///         "/domain/zone/example.com/record",
+    ///         |id: &u64| format!("/domain/zone/example.com/record/{}", id),
+    ///         8,
+    ///     )
+    ///     .try_collect()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_and_fetch<'a, I, T, F>(
+        &'a self,
+        list_path: &str,
+        item_path: F,
+        concurrency: usize,
+    ) -> impl Stream<Item = Result<T>> + 'a
+    where
+        I: DeserializeOwned + 'a,
+        T: DeserializeOwned + 'a,
+        F: Fn(&I) -> String + 'a,
+    {
+        let list = self.request(Method::GET, list_path);
+        self.fetch_listed(list, item_path, concurrency)
+    }
+
+    /// Lists IDs with query parameters, then fetches the corresponding
+    /// objects, as [`OvhClient::list_and_fetch`] does.
+    ///
+    /// ```no_run
+    /// # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+    /// use futures::TryStreamExt;
+    ///
+    /// let records: Vec<serde_json::Value> = client
+    ///     .list_and_fetch_with_query(
+    ///         "/domain/zone/example.com/record",
+    ///         &[("fieldType", "MX")],
+    ///         |id: &u64| format!("/domain/zone/example.com/record/{}", id),
+    ///         8,
+    ///     )
+    ///     .try_collect()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn list_and_fetch_with_query<'a, Q, I, T, F>(
+        &'a self,
+        list_path: &str,
+        query: &Q,
+        item_path: F,
+        concurrency: usize,
+    ) -> impl Stream<Item = Result<T>> + 'a
+    where
+        Q: Serialize + ?Sized,
+        I: DeserializeOwned + 'a,
+        T: DeserializeOwned + 'a,
+        F: Fn(&I) -> String + 'a,
+    {
+        let list = self.request(Method::GET, list_path).query(query);
+        self.fetch_listed(list, item_path, concurrency)
+    }
+
+    fn fetch_listed<'a, I, T, F>(
+        &'a self,
+        list: RequestBuilder<'a>,
+        item_path: F,
+        concurrency: usize,
+    ) -> impl Stream<Item = Result<T>> + 'a
+    where
+        I: DeserializeOwned + 'a,
+        T: DeserializeOwned + 'a,
+        F: Fn(&I) -> String + 'a,
+    {
+        let ids = stream::once(list.send_json::<Vec<I>>())
+            .map_ok(|ids| stream::iter(ids.into_iter().map(Ok)))
+            .try_flatten();
+
+        ids.map_ok(move |id| {
+            let path = item_path(&id);
+            async move { self.get_json::<T>(&path).await }
+        })
+        .try_buffered(concurrency.max(1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::stub::{Response, StubServer};
+    use super::*;
+    use crate::client::Error;
+    use futures::StreamExt;
+
+    // Serves `pages` in order, each page but the last pointing to the next.
+    async fn paged_server(pages: &'static [&'static str]) -> StubServer {
+        StubServer::start(move |req| {
+            let page = match req.header("X-Pagination-Cursor") {
+                Some(cursor) => cursor.trim_start_matches("page-").parse().unwrap(),
+                None => 0,
+            };
+            let resp = Response::json(pages[page]);
+            if page + 1 < pages.len() {
+                resp.header("X-Pagination-Cursor-Next", &format!("page-{}", page + 1))
+            } else {
+                resp
+            }
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn follows_cursors() {
+        let server = paged_server(&["[1, 2]", "[3]", "[]", "[4]"]).await;
+        let client = server.client();
+
+        let items: Vec<u32> = client.paginate::<u32>("/domain", 2).try_collect().await.unwrap();
+        assert_eq!(items, vec![1, 2, 3, 4]);
+
+        let requests = server.requests();
+        let cursors: Vec<_> = requests
+            .iter()
+            .map(|r| r.header("X-Pagination-Cursor"))
+            .collect();
+        assert_eq!(cursors, vec![None, Some("page-1"), Some("page-2"), Some("page-3")]);
+        for req in &requests {
+            assert_eq!(req.method, "GET");
+            assert_eq!(req.path, "/1.0/domain");
+            assert_eq!(req.header("X-Pagination-Size"), Some("2"));
+            assert_eq!(
+                req.header("X-Pagination-Mode"),
+                Some("CachedObjectList-Pages")
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn v2_pages_need_no_mode() {
+        let server = StubServer::start(|_| Response::json("[1]")).await;
+        let client = server.client();
+
+        let items: Vec<u32> = client
+            .paginate_v2::<u32>("/iam/policy", 10)
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(items, vec![1]);
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].path, "/v2/iam/policy");
+        assert_eq!(requests[0].header("X-Pagination-Mode"), None);
+    }
+
+    #[tokio::test]
+    async fn stops_on_empty_cursor() {
+        let server =
+            StubServer::start(|_| Response::json("[1]").header("X-Pagination-Cursor-Next", ""))
+                .await;
+        let client = server.client();
+
+        let items: Vec<u32> = client.paginate::<u32>("/domain", 10).try_collect().await.unwrap();
+        assert_eq!(items, vec![1]);
+        assert_eq!(server.requests().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn stops_on_error() {
+        let server = StubServer::start(|req| match req.header("X-Pagination-Cursor") {
+            None => Response::json("[1]").header("X-Pagination-Cursor-Next", "next"),
+            Some(_) => Response::new(404, r#"{"message": "cursor expired"}"#)
+                .header("X-Pagination-Cursor-Next", "next"),
+        })
+        .await;
+        let client = server.client();
+
+        let items: Vec<Result<u32>> = client.paginate::<u32>("/domain", 10).collect().await;
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].as_ref().unwrap(), &1);
+        match &items[1] {
+            Err(Error::ApiError(e)) => assert_eq!(e.message, "cursor expired"),
+            other => panic!("unexpected item: {:?}", other),
+        }
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn fetches_listed_ids_in_order() {
+        let server = StubServer::start(|req| match req.path.as_str() {
+            "/1.0/domain/zone/example.com/record?fieldType=MX&subDomain=mail" => {
+                Response::json("[3, 1, 2]")
+            }
+            path => Response::json(path.trim_start_matches("/1.0/domain/zone/example.com/record/")),
+        })
+        .await;
+        let client = server.client();
+
+        let records: Vec<_> = client
+            .list_and_fetch_with_query::<_, u64, u64, _>(
+                "/domain/zone/example.com/record",
+                &[("fieldType", "MX"), ("subDomain", "mail")],
+                |id: &u64| format!("/domain/zone/example.com/record/{}", id),
+                2,
+            )
+            .try_collect()
+            .await
+            .unwrap();
+        assert_eq!(records, vec![3, 1, 2]);
+        assert_eq!(server.requests().len(), 4);
+    }
+}
@@ -0,0 +1,259 @@
+//! Builder for arbitrary requests.
+
+use super::{auth::Auth, check_status, decode_json, ApiVersion, Error, OvhClient, Result};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    Method, Response, StatusCode, Url,
+};
+use serde::{de::DeserializeOwned, Serialize};
+use std::time::Duration;
+
+/// Request to the OVH API, created by [`OvhClient::request`].
+///
+/// Requests are signed by default. Errors raised while building the
+/// request, e.g. when serializing the body, are reported by
+/// [`RequestBuilder::send`].
+///
+/// ```no_run
+/// # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+/// use ovh::client::Method;
+/// use std::time::Duration;
+///
+/// let resp = client
+///     .request(Method::GET, "/domain/zone/example.com/record")
+///     .query(&[("fieldType", "MX")])
+///     .header("X-Pagination-Mode", "CachedObjectList-Pages")
+///     .timeout(Duration::from_secs(10))
+///     .send()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct RequestBuilder<'a> {
+    client: &'a OvhClient,
+    method: Method,
+    api_version: ApiVersion,
+    path: String,
+    query: Option<String>,
+    body: Option<String>,
+    headers: HeaderMap,
+    timeout: Option<Duration>,
+    signed: bool,
+    error: Option<Error>,
+}
+
+impl<'a> RequestBuilder<'a> {
+    pub(super) fn new(client: &'a OvhClient, method: Method, path: &str) -> Self {
+        RequestBuilder {
+            client,
+            method,
+            api_version: ApiVersion::V1,
+            path: path.into(),
+            query: None,
+            body: None,
+            headers: HeaderMap::new(),
+            timeout: None,
+            signed: true,
+            error: None,
+        }
+    }
+
+    fn fail(mut self, error: Error) -> Self {
+        self.error.get_or_insert(error);
+        self
+    }
+
+    /// Sets the API version targeted by the request, `/1.0` by default.
+    ///
+    /// ```no_run
+    /// # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+    /// use ovh::client::{ApiVersion, Method};
+    ///
+    /// let policies: Vec<serde_json::Value> = client
+    ///     .request(Method::GET, "/iam/policy")
+    ///     .api_version(ApiVersion::V2)
+    ///     .send_json()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn api_version(mut self, version: ApiVersion) -> Self {
+        self.api_version = version;
+        self
+    }
+
+    /// Adds query parameters, percent-encoded before signing the request.
+    pub fn query<Q: Serialize + ?Sized>(mut self, query: &Q) -> Self {
+        let encoded = match serde_urlencoded::to_string(query) {
+            Ok(encoded) => encoded,
+            Err(e) => return self.fail(Error::Error(e.into())),
+        };
+
+        if !encoded.is_empty() {
+            match &mut self.query {
+                Some(query) => {
+                    query.push('&');
+                    query.push_str(&encoded);
+                }
+                None => self.query = Some(encoded),
+            }
+        }
+        self
+    }
+
+    /// Sets a JSON body.
+    pub fn json<T: Serialize + ?Sized>(mut self, body: &T) -> Self {
+        // Cannot call reqwest's RequestBuilder.json directly because of
+        // body signature requirement.
+        match serde_json::to_string(body) {
+            Ok(body) => {
+                self.body = Some(body);
+                self
+            }
+            Err(e) => self.fail(e.into()),
+        }
+    }
+
+    /// Adds a header, e.g. `X-Pagination-Mode` or `X-Ovh-Batch`.
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        let name = match HeaderName::from_bytes(name.as_bytes()) {
+            Ok(name) => name,
+            Err(e) => return self.fail(Error::Error(e.into())),
+        };
+        let value = match HeaderValue::from_str(value) {
+            Ok(value) => value,
+            Err(e) => return self.fail(Error::Error(e.into())),
+        };
+
+        self.headers.insert(name, value);
+        self
+    }
+
+    /// Sets a timeout for this request only, overriding the one of the
+    /// client.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Sets whether the request is authenticated. Defaults to `true`.
+    pub fn signed(mut self, signed: bool) -> Self {
+        self.signed = signed;
+        self
+    }
+
+    /// Builds the URL of the request.
+    ///
+    /// The URL is normalized the same way it will be when sent, since
+    /// it is part of the request signature.
+    fn url(&self) -> Result<String> {
+        let mut url = self.client.url(self.api_version, &self.path)?;
+
+        if let Some(query) = &self.query {
+            url.push(if url.contains('?') { '&' } else { '?' });
+            url.push_str(query);
+        }
+
+        let url = Url::parse(&url).map_err(|e| Error::Error(e.into()))?;
+        Ok(url.into())
+    }
+
+    async fn headers(&self, url: &str) -> Result<HeaderMap> {
+        let mut headers = if self.signed {
+            let body = self.body.as_deref().unwrap_or("");
+            self.client
+                .gen_headers(url, self.method.as_str(), body)
+                .await?
+        } else {
+            self.client.default_headers()
+        };
+
+        if self.body.is_some() {
+            headers.insert("Content-type", "application/json".parse().unwrap());
+        }
+        headers.extend(self.headers.clone());
+
+        Ok(headers)
+    }
+
+    /// Sends the request.
+    ///
+    /// Non-successful responses are turned into an [`Error::ApiError`].
+    /// Transient failures are retried according to the
+    /// [`RetryPolicy`](super::RetryPolicy) of the client.
+    pub async fn send(self) -> Result<Response> {
+        if let Some(error) = self.error {
+            return Err(error);
+        }
+
+        let client = self.client;
+        let url = self.url()?;
+        let mut resynced = false;
+        let mut reauthenticated = false;
+        let mut attempt = 0;
+
+        loop {
+            attempt += 1;
+
+            // Waiting for the limits first, so that the request is not
+            // sent with a timestamp or token that went stale meanwhile.
+            let permit = client.limits.acquire().await;
+
+            // Signing again for every attempt, so that retries do not
+            // reuse a stale timestamp.
+            let headers = self.headers(&url).await?;
+
+            let mut req = client
+                .client
+                .request(self.method.clone(), &url)
+                .headers(headers);
+            if let Some(body) = &self.body {
+                req = req.body(body.clone());
+            }
+            if let Some(timeout) = self.timeout {
+                req = req.timeout(timeout);
+            }
+
+            let res = match req.send().await {
+                Ok(resp) => check_status(resp).await,
+                Err(e) => Err(e.into()),
+            };
+            drop(permit);
+
+            match res {
+                // The local clock may have drifted since the delta was
+                // computed: sync it again and retry once.
+                Err(Error::ApiError(e))
+                    if self.signed && e.is_invalid_timestamp() && !resynced =>
+                {
+                    client.refresh_time_delta().await?;
+                    resynced = true;
+                }
+                // The access token may have been revoked before its
+                // expiry: get a new one and retry once.
+                Err(Error::ApiError(e))
+                    if self.signed && e.status == StatusCode::UNAUTHORIZED && !reauthenticated =>
+                {
+                    match &client.auth {
+                        Auth::OAuth2(oauth2) => oauth2.invalidate().await,
+                        Auth::ApplicationKey { .. } => return Err(Error::ApiError(e)),
+                    }
+                    reauthenticated = true;
+                }
+                Err(e) => match client.retry_policy.delay(&self.method, &e, attempt) {
+                    Some(delay) => tokio::time::sleep(delay).await,
+                    None => return Err(e),
+                },
+                res => return res,
+            }
+        }
+    }
+
+    /// Sends the request and deserializes the response body.
+    ///
+    /// Use `()` as `T` for calls that do not return anything.
+    pub async fn send_json<T: DeserializeOwned>(self) -> Result<T> {
+        let resp = self.send().await?;
+        decode_json(resp).await
+    }
+}
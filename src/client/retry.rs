@@ -0,0 +1,250 @@
+//! Retries of requests failing because of transient errors.
+
+use super::{random_u64, Error};
+use reqwest::Method;
+use std::time::Duration;
+
+/// Policy for retrying requests that failed because of transient
+/// errors: connection failures, timeouts, `429 Too Many Requests` and
+/// `5xx` responses.
+///
+/// Delays grow exponentially between attempts, with full jitter. A
+/// `Retry-After` header sent by the API is honoured, unless it asks to
+/// wait for longer than the maximum delay, in which case the request is
+/// not retried.
+///
+/// Only idempotent methods (GET, PUT, DELETE) are retried by default.
+///
+/// ```
+/// use ovh::client::{OvhClient, RetryPolicy};
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new()
+///     .max_attempts(5)
+///     .base_delay(Duration::from_millis(200))
+///     .retry_post(true);
+///
+/// let client = OvhClient::builder()
+///     .endpoint(ovh::client::Endpoint::OvhEu)
+///     .application_key("my_app_key")
+///     .application_secret("my_app_secret")
+///     .retry_policy(policy)
+///     .build();
+/// assert!(client.is_ok());
+/// ```
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    retry_post: bool,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: 3,
+            base_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            retry_post: false,
+        }
+    }
+}
+
+fn jitter(max: Duration) -> Duration {
+    let random = random_u64();
+    max.mul_f64(random as f64 / u64::MAX as f64)
+}
+
+impl RetryPolicy {
+    /// Creates the default policy: 3 attempts, with delays starting at
+    /// 500 ms and capped at 30 s.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a policy that never retries.
+    pub fn none() -> Self {
+        Self::default().max_attempts(1)
+    }
+
+    /// Sets the maximum number of attempts, including the first one.
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Sets the maximum delay before the first retry.
+    pub fn base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Sets the maximum delay between two attempts.
+    pub fn max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Sets whether POST requests are retried as well.
+    ///
+    /// POST requests are usually not idempotent, so retrying them may
+    /// perform the same action twice.
+    pub fn retry_post(mut self, retry_post: bool) -> Self {
+        self.retry_post = retry_post;
+        self
+    }
+
+    /// Returns how long to wait before retrying a request that failed
+    /// with `error` at the given attempt, or `None` to give up.
+    pub(super) fn delay(&self, method: &Method, error: &Error, attempt: u32) -> Option<Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+
+        let retryable = match *method {
+            Method::GET | Method::PUT | Method::DELETE => true,
+            Method::POST => self.retry_post,
+            _ => false,
+        };
+        if !retryable {
+            return None;
+        }
+
+        let retry_after = match error {
+            Error::ApiError(e) if e.is_rate_limited() || e.status.is_server_error() => e.retry_after,
+            Error::Error(e) => match e.downcast_ref::<reqwest::Error>() {
+                Some(e) if e.is_connect() || e.is_timeout() => None,
+                _ => return None,
+            },
+            _ => return None,
+        };
+
+        match retry_after {
+            Some(delay) if delay > self.max_delay => None,
+            Some(delay) => Some(delay),
+            None => {
+                let factor = 2u32.saturating_pow(attempt - 1);
+                let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+                Some(jitter(delay))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::ApiError;
+    use reqwest::StatusCode;
+
+    fn api_error(status: u16, retry_after: Option<u64>) -> Error {
+        ApiError {
+            status: StatusCode::from_u16(status).unwrap(),
+            message: String::new(),
+            error_code: None,
+            http_code: None,
+            details: None,
+            class: None,
+            query_id: None,
+            retry_after: retry_after.map(Duration::from_secs),
+        }
+        .into()
+    }
+
+    #[test]
+    fn retries_idempotent_methods_only() {
+        let policy = RetryPolicy::new();
+        let error = api_error(503, Some(1));
+
+        for method in [Method::GET, Method::PUT, Method::DELETE] {
+            assert_eq!(policy.delay(&method, &error, 1), Some(Duration::from_secs(1)));
+        }
+        assert_eq!(policy.delay(&Method::POST, &error, 1), None);
+        assert_eq!(policy.delay(&Method::PATCH, &error, 1), None);
+
+        let policy = policy.retry_post(true);
+        assert_eq!(policy.delay(&Method::POST, &error, 1), Some(Duration::from_secs(1)));
+    }
+
+    #[test]
+    fn stops_after_max_attempts() {
+        let policy = RetryPolicy::new().max_attempts(2);
+        let error = api_error(503, Some(1));
+
+        assert!(policy.delay(&Method::GET, &error, 1).is_some());
+        assert_eq!(policy.delay(&Method::GET, &error, 2), None);
+        assert_eq!(RetryPolicy::none().delay(&Method::GET, &error, 1), None);
+    }
+
+    #[test]
+    fn retries_rate_limits_and_server_errors_only() {
+        let policy = RetryPolicy::new();
+
+        assert!(policy.delay(&Method::GET, &api_error(429, None), 1).is_some());
+        assert!(policy.delay(&Method::GET, &api_error(500, None), 1).is_some());
+        for status in [400, 401, 403, 404] {
+            assert_eq!(policy.delay(&Method::GET, &api_error(status, None), 1), None);
+        }
+        let error = Error::ConfigError("missing consumer key".to_string());
+        assert_eq!(policy.delay(&Method::GET, &error, 1), None);
+    }
+
+    #[test]
+    fn gives_up_when_retry_after_exceeds_max_delay() {
+        let policy = RetryPolicy::new().max_delay(Duration::from_secs(10));
+
+        let delay = policy.delay(&Method::GET, &api_error(429, Some(10)), 1);
+        assert_eq!(delay, Some(Duration::from_secs(10)));
+        assert_eq!(policy.delay(&Method::GET, &api_error(429, Some(11)), 1), None);
+    }
+
+    #[test]
+    fn caps_exponential_backoff() {
+        let policy = RetryPolicy::new()
+            .max_attempts(20)
+            .base_delay(Duration::from_secs(1))
+            .max_delay(Duration::from_secs(4));
+        let error = api_error(503, None);
+
+        for attempt in 1..20 {
+            let cap = Duration::from_secs(1 << (attempt - 1).min(2));
+            for _ in 0..20 {
+                let delay = policy.delay(&Method::GET, &error, attempt).unwrap();
+                assert!(delay <= cap, "attempt {}: {:?} > {:?}", attempt, delay, cap);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn retries_connection_failures_and_timeouts_only() {
+        let policy = RetryPolicy::new();
+        let client = reqwest::Client::new();
+
+        // Nothing listens on a port just released.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        drop(listener);
+        let error: Error = client
+            .get(format!("http://{}", addr))
+            .send()
+            .await
+            .unwrap_err()
+            .into();
+        assert!(policy.delay(&Method::GET, &error, 1).is_some());
+
+        // A listener never answering makes the request time out.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let error: Error = client
+            .get(format!("http://{}", listener.local_addr().unwrap()))
+            .timeout(Duration::from_millis(50))
+            .send()
+            .await
+            .unwrap_err()
+            .into();
+        assert!(policy.delay(&Method::GET, &error, 1).is_some());
+
+        let error: Error = client.get("not a url").send().await.unwrap_err().into();
+        assert_eq!(policy.delay(&Method::GET, &error, 1), None);
+    }
+}
@@ -0,0 +1,172 @@
+//! Local HTTP server standing in for the API in tests.
+
+use super::{now, Endpoint, OvhClient, RetryPolicy};
+use std::{
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader},
+    net::{TcpListener, TcpStream},
+    time::Instant,
+};
+
+/// Request received by a [`StubServer`].
+#[derive(Debug, Clone)]
+pub(crate) struct Request {
+    pub method: String,
+    /// Path of the request, with its query string.
+    pub path: String,
+    pub headers: Vec<(String, String)>,
+    pub received_at: Instant,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+}
+
+/// Response sent back by a [`StubServer`].
+#[derive(Debug, Clone)]
+pub(crate) struct Response {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: String,
+}
+
+impl Response {
+    pub fn new(status: u16, body: &str) -> Self {
+        Response {
+            status,
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    pub fn json(body: &str) -> Self {
+        Response::new(200, body)
+    }
+
+    pub fn header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+type Handler = dyn Fn(&Request) -> Response + Send + Sync;
+
+/// Server answering each request with `handler`, except for
+/// `/1.0/auth/time` which it answers itself.
+pub(crate) struct StubServer {
+    addr: SocketAddr,
+    requests: Arc<Mutex<Vec<Request>>>,
+}
+
+impl StubServer {
+    pub async fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&Request) -> Response + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let handler: Arc<Handler> = Arc::new(handler);
+
+        let received = requests.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let handler = handler.clone();
+                let received = received.clone();
+                tokio::spawn(async move {
+                    let _ = serve(stream, &*handler, &received).await;
+                });
+            }
+        });
+
+        StubServer { addr, requests }
+    }
+
+    /// Client of the server, without retries nor pooled connections.
+    pub fn client(&self) -> OvhClient {
+        let url = format!("http://{}/1.0", self.addr).parse().unwrap();
+        OvhClient::builder()
+            .endpoint(Endpoint::Custom(url))
+            .application_key("key")
+            .application_secret("secret")
+            .consumer_key("consumer")
+            .no_proxy()
+            .pool_idle_timeout(None)
+            .pool_max_idle_per_host(0)
+            .retry_policy(RetryPolicy::none())
+            .build()
+            .unwrap()
+    }
+
+    /// Requests received so far, but the ones for the time.
+    pub fn requests(&self) -> Vec<Request> {
+        self.requests.lock().unwrap().clone()
+    }
+}
+
+async fn serve(
+    stream: TcpStream,
+    handler: &Handler,
+    received: &Mutex<Vec<Request>>,
+) -> std::io::Result<()> {
+    let mut stream = BufReader::new(stream);
+
+    let mut line = String::new();
+    stream.read_line(&mut line).await?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut headers = Vec::new();
+    loop {
+        line.clear();
+        stream.read_line(&mut line).await?;
+        match line.trim_end().split_once(':') {
+            Some((name, value)) => headers.push((name.to_string(), value.trim().to_string())),
+            None => break,
+        }
+    }
+
+    let request = Request {
+        method,
+        path,
+        headers,
+        received_at: Instant::now(),
+    };
+    let len = request
+        .header("Content-Length")
+        .and_then(|l| l.parse().ok())
+        .unwrap_or(0);
+    stream.read_exact(&mut vec![0; len]).await?;
+
+    let response = if request.path == "/1.0/auth/time" {
+        Response::json(&now().to_string())
+    } else {
+        let response = handler(&request);
+        received.lock().unwrap().push(request);
+        response
+    };
+
+    let mut head = format!(
+        "HTTP/1.1 {} Stub\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        response.body.len()
+    );
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{}: {}\r\n", name, value));
+    }
+    head.push_str("\r\n");
+
+    let stream = stream.get_mut();
+    stream.write_all(head.as_bytes()).await?;
+    stream.write_all(response.body.as_bytes()).await?;
+    stream.shutdown().await
+}
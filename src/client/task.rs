@@ -0,0 +1,265 @@
+//! Asynchronous operations, returned by many endpoints as tasks to be
+//! followed until they complete.
+
+use super::{OvhClient, Result};
+use serde::Deserialize;
+use std::time::Duration;
+use tokio::time::{sleep, Instant};
+
+/// Status of a [`Task`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TaskStatus {
+    Cancelled,
+    CustomerError,
+    Doing,
+    Done,
+    Error,
+    Init,
+    OvhError,
+    Todo,
+    /// Status not known by this crate.
+    #[serde(other)]
+    Unknown,
+}
+
+impl TaskStatus {
+    /// Whether the task will not change anymore.
+    ///
+    /// ```
+    /// use ovh::client::TaskStatus;
+    ///
+    /// assert!(TaskStatus::CustomerError.is_terminal());
+    /// assert!(!TaskStatus::Todo.is_terminal());
+    /// ```
+    pub fn is_terminal(self) -> bool {
+        match self {
+            TaskStatus::Cancelled
+            | TaskStatus::CustomerError
+            | TaskStatus::Done
+            | TaskStatus::Error
+            | TaskStatus::OvhError => true,
+            TaskStatus::Doing | TaskStatus::Init | TaskStatus::Todo | TaskStatus::Unknown => false,
+        }
+    }
+}
+
+/// Asynchronous operation, such as a zone import or a server reboot.
+///
+/// Products name some fields differently; the dates only set by some of
+/// them are optional.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Task {
+    #[serde(alias = "taskId")]
+    pub id: u64,
+    pub function: String,
+    pub status: TaskStatus,
+    pub comment: Option<String>,
+    pub creation_date: Option<String>,
+    pub start_date: Option<String>,
+    pub todo_date: Option<String>,
+    pub done_date: Option<String>,
+    pub last_update: Option<String>,
+}
+
+/// Final state of a task followed with [`OvhClient::wait_for_task`].
+#[derive(Debug, Clone)]
+pub enum TaskOutcome {
+    Done(Task),
+    /// The task failed, because of an error of the customer or of OVH.
+    Failed(Task),
+    Cancelled(Task),
+    /// The task was still running when the wait timed out.
+    TimedOut(Task),
+}
+
+impl TaskOutcome {
+    /// Last known state of the task.
+    pub fn task(&self) -> &Task {
+        match self {
+            TaskOutcome::Done(task)
+            | TaskOutcome::Failed(task)
+            | TaskOutcome::Cancelled(task)
+            | TaskOutcome::TimedOut(task) => task,
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        matches!(self, TaskOutcome::Done(_))
+    }
+}
+
+/// How a task is polled by [`OvhClient::wait_for_task`].
+///
+/// The task is polled at growing intervals, from 1 s up to 30 s, for at
+/// most 30 minutes by default.
+///
+/// ```
+/// use ovh::client::WaitOptions;
+/// use std::time::Duration;
+///
+/// let options = WaitOptions::new()
+///     .initial_interval(Duration::from_secs(5))
+///     .timeout(Duration::from_secs(3600));
+/// ```
+#[derive(Debug, Clone)]
+pub struct WaitOptions {
+    initial_interval: Duration,
+    max_interval: Duration,
+    timeout: Duration,
+}
+
+impl Default for WaitOptions {
+    fn default() -> Self {
+        WaitOptions {
+            initial_interval: Duration::from_secs(1),
+            max_interval: Duration::from_secs(30),
+            timeout: Duration::from_secs(30 * 60),
+        }
+    }
+}
+
+impl WaitOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the delay before the second poll, doubled after each poll.
+    pub fn initial_interval(mut self, interval: Duration) -> Self {
+        self.initial_interval = interval;
+        self
+    }
+
+    /// Sets the maximum delay between polls.
+    pub fn max_interval(mut self, interval: Duration) -> Self {
+        self.max_interval = interval;
+        self
+    }
+
+    /// Sets the time after which the wait gives up, [`Duration::MAX`]
+    /// waiting forever.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+impl OvhClient {
+    /// Polls the task at `path` until it completes or the wait times out.
+    ///
+    /// ```no_run
+    /// # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+    /// use ovh::client::{TaskOutcome, WaitOptions};
+    ///
+    /// let path = "/domain/zone/example.com/task/1234";
+    /// match client.wait_for_task(path, &WaitOptions::new()).await? {
+    ///     TaskOutcome::Done(_) => println!("done"),
+    ///     outcome => println!("task ended with {:?}", outcome.task().status),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn wait_for_task(&self, path: &str, options: &WaitOptions) -> Result<TaskOutcome> {
+        // A timeout too large to be represented means no deadline.
+        let deadline = Instant::now().checked_add(options.timeout);
+        let mut interval = options.initial_interval;
+
+        loop {
+            let task: Task = self.get_json(path).await?;
+            match task.status {
+                TaskStatus::Done => return Ok(TaskOutcome::Done(task)),
+                TaskStatus::Cancelled => return Ok(TaskOutcome::Cancelled(task)),
+                status if status.is_terminal() => return Ok(TaskOutcome::Failed(task)),
+                _ => {}
+            }
+
+            let remaining = match deadline {
+                Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+                None => Duration::MAX,
+            };
+            if remaining.is_zero() {
+                return Ok(TaskOutcome::TimedOut(task));
+            }
+            sleep(interval.min(remaining)).await;
+            interval = interval.saturating_mul(2).min(options.max_interval);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::stub::{Response, StubServer};
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    const PATH: &str = "/domain/zone/example.com/task/1234";
+
+    // Serves a task done at the given poll, starting from 1.
+    async fn task_server(done_at: usize) -> StubServer {
+        let polls = AtomicUsize::new(0);
+        StubServer::start(move |_| {
+            let poll = polls.fetch_add(1, Ordering::SeqCst) + 1;
+            let status = if poll >= done_at { "done" } else { "doing" };
+            Response::json(&format!(
+                r#"{{"id": 1234, "function": "DnsZoneImport", "status": "{}"}}"#,
+                status
+            ))
+        })
+        .await
+    }
+
+    fn poll_times(server: &StubServer) -> Vec<Duration> {
+        let requests = server.requests();
+        let start = requests[0].received_at;
+        requests.iter().map(|r| r.received_at - start).collect()
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn backs_off_up_to_max_interval() {
+        let server = task_server(6).await;
+        let options = WaitOptions::new()
+            .initial_interval(Duration::from_secs(1))
+            .max_interval(Duration::from_secs(4));
+
+        let outcome = server.client().wait_for_task(PATH, &options).await.unwrap();
+        assert!(outcome.is_done());
+        assert_eq!(outcome.task().id, 1234);
+
+        let expected: Vec<_> = [0, 1, 3, 7, 11, 15]
+            .iter()
+            .map(|s| Duration::from_secs(*s))
+            .collect();
+        assert_eq!(poll_times(&server), expected);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn times_out() {
+        let server = task_server(usize::MAX).await;
+        let options = WaitOptions::new()
+            .initial_interval(Duration::from_secs(4))
+            .timeout(Duration::from_secs(10));
+
+        let outcome = server.client().wait_for_task(PATH, &options).await.unwrap();
+        match outcome {
+            TaskOutcome::TimedOut(task) => assert_eq!(task.status, TaskStatus::Doing),
+            outcome => panic!("unexpected outcome: {:?}", outcome),
+        }
+        // The last poll happens at the deadline.
+        let expected: Vec<_> = [0, 4, 10].iter().map(|s| Duration::from_secs(*s)).collect();
+        assert_eq!(poll_times(&server), expected);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn accepts_unbounded_durations() {
+        let server = task_server(3).await;
+        let options = WaitOptions::new()
+            .initial_interval(Duration::MAX / 2 + Duration::from_secs(1))
+            .max_interval(Duration::MAX)
+            .timeout(Duration::MAX);
+
+        let outcome = server.client().wait_for_task(PATH, &options).await.unwrap();
+        assert!(outcome.is_done());
+        assert_eq!(server.requests().len(), 3);
+    }
+}
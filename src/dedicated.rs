@@ -0,0 +1,3 @@
+//! Dedicated servers and their related services.
+
+pub mod server;
@@ -0,0 +1,530 @@
+//! Dedicated servers, under `/dedicated/server`.
+//!
+//! ```no_run
+//! # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+//! use ovh::{
+//!     client::WaitOptions,
+//!     dedicated::server::{self, BootType},
+//! };
+//!
+//! let name = "ns1234567.ip-203-0-113.eu";
+//! server::set_boot_type(&client, name, BootType::Rescue).await?;
+//! let task = server::reboot(&client, name).await?;
+//! let outcome = server::wait_for_task(&client, name, task.id, &WaitOptions::new()).await?;
+//! assert!(outcome.is_done());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::{
+    encode_path_segment, Error, Method, OvhClient, Result, Task, TaskOutcome, WaitOptions,
+};
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+pub mod backup_ftp;
+pub mod intervention;
+pub mod monitoring;
+pub mod statistics;
+
+/// State of a server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ServerState {
+    Error,
+    Hacked,
+    HackedBlocked,
+    Ok,
+}
+
+/// Power state of a server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PowerState {
+    PowerOff,
+    PowerOn,
+}
+
+/// Dedicated server.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Server {
+    pub server_id: u64,
+    pub name: String,
+    pub ip: IpAddr,
+    pub reverse: Option<String>,
+    pub datacenter: String,
+    pub rack: String,
+    pub commercial_range: Option<String>,
+    pub os: String,
+    pub state: ServerState,
+    pub power_state: PowerState,
+    /// Netboot the server starts on, unset for the default one.
+    pub boot_id: Option<u64>,
+    pub monitoring: bool,
+    pub no_intervention: bool,
+    pub professional_use: bool,
+    pub rescue_mail: Option<String>,
+    pub root_device: Option<String>,
+    pub support_level: String,
+    /// Link speed, in Mbps.
+    pub link_speed: Option<u32>,
+}
+
+/// Changes to apply to a server, unset fields being kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub boot_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monitoring: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_intervention: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rescue_mail: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root_device: Option<String>,
+}
+
+/// Quantity along with its unit, e.g. 32 GB.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Size {
+    pub unit: String,
+    pub value: f64,
+}
+
+/// Group of identical disks of a server.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DiskGroup {
+    pub disk_group_id: u64,
+    pub description: String,
+    pub disk_type: String,
+    pub disk_size: Size,
+    pub number_of_disks: u32,
+    pub raid_controller: Option<String>,
+    pub default_hardware_raid_type: Option<String>,
+    pub default_hardware_raid_size: Option<Size>,
+}
+
+/// Hardware specifications of a server.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct HardwareSpecifications {
+    pub description: Option<String>,
+    pub form_factor: Option<String>,
+    pub motherboard: Option<String>,
+    pub boot_mode: Option<String>,
+    pub processor_name: Option<String>,
+    pub processor_architecture: Option<String>,
+    pub number_of_processors: Option<u32>,
+    pub cores_per_processor: Option<u32>,
+    pub threads_per_processor: Option<u32>,
+    pub memory_size: Option<Size>,
+    #[serde(default)]
+    pub disk_groups: Vec<DiskGroup>,
+}
+
+/// Kind of a netboot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum BootType {
+    /// Operating system installed on the disks.
+    Harddisk,
+    Internal,
+    IpxeCustomerScript,
+    Network,
+    Rescue,
+}
+
+/// Netboot a server can start on.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Boot {
+    pub boot_id: u64,
+    pub boot_type: BootType,
+    pub description: String,
+    pub kernel: String,
+}
+
+/// Installation templates a server can be installed with.
+#[derive(Debug, Clone, Deserialize)]
+pub struct CompatibleTemplates {
+    /// Templates provided by OVH.
+    pub ovh: Vec<String>,
+    /// Templates of the account, under `/me/installationTemplate`.
+    pub personal: Vec<String>,
+}
+
+/// Options of an installation, on top of its template.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallDetails {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub custom_hostname: Option<String>,
+    /// Name of an SSH key of the account, under `/me/sshKey`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ssh_key_name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub disk_group_id: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub no_raid: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub soft_raid_devices: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub use_distrib_kernel: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_installation_script_link: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub post_installation_script_return: Option<String>,
+}
+
+/// Installation of a server.
+///
+/// ```
+/// use ovh::dedicated::server::Install;
+///
+/// let install = Install::new("debian12_64")
+///     .partition_scheme("default")
+///     .hostname("web1.example.com")
+///     .ssh_key("deploy");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Install {
+    template_name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    partition_scheme_name: Option<String>,
+    details: InstallDetails,
+}
+
+impl Install {
+    /// Installs the given template, with its default partition scheme.
+    pub fn new(template: &str) -> Self {
+        Install {
+            template_name: template.into(),
+            partition_scheme_name: None,
+            details: InstallDetails::default(),
+        }
+    }
+
+    /// Uses the given partition scheme of the template.
+    pub fn partition_scheme(mut self, name: &str) -> Self {
+        self.partition_scheme_name = Some(name.into());
+        self
+    }
+
+    /// Sets the hostname of the installed system.
+    pub fn hostname(mut self, hostname: &str) -> Self {
+        self.details.custom_hostname = Some(hostname.into());
+        self
+    }
+
+    /// Installs an SSH key of the account.
+    pub fn ssh_key(mut self, name: &str) -> Self {
+        self.details.ssh_key_name = Some(name.into());
+        self
+    }
+
+    /// Sets all the options of the installation.
+    pub fn details(mut self, details: InstallDetails) -> Self {
+        self.details = details;
+        self
+    }
+}
+
+/// Step of a running installation.
+#[derive(Debug, Clone, Deserialize)]
+pub struct InstallStep {
+    pub status: String,
+    pub comment: String,
+    pub error: Option<String>,
+}
+
+/// Progress of a running installation.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InstallStatus {
+    /// Time elapsed since the start of the installation, in seconds.
+    pub elapsed_time: u64,
+    pub progress: Vec<InstallStep>,
+}
+
+/// Kind of access to the IPMI of a server.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum IpmiAccessType {
+    /// KVM in a browser.
+    #[serde(rename = "kvmipHtml5URL")]
+    KvmHtml5Url,
+    /// KVM as a Java Web Start application.
+    #[serde(rename = "kvmipJnlp")]
+    KvmJnlp,
+    /// Serial console in a browser.
+    #[serde(rename = "serialOverLanURL")]
+    SerialOverLanUrl,
+    /// Serial console over SSH.
+    #[serde(rename = "serialOverLanSshKey")]
+    SerialOverLanSshKey,
+}
+
+/// Access methods supported by the IPMI of a server.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IpmiSupportedFeatures {
+    #[serde(rename = "kvmipHtml5URL")]
+    pub kvm_html5_url: bool,
+    #[serde(rename = "kvmipJnlp")]
+    pub kvm_jnlp: bool,
+    #[serde(rename = "serialOverLanURL")]
+    pub serial_over_lan_url: bool,
+    pub serial_over_lan_ssh_key: bool,
+}
+
+/// IPMI of a server.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ipmi {
+    pub activated: bool,
+    pub supported_features: IpmiSupportedFeatures,
+}
+
+/// Request for a temporary access to the IPMI of a server.
+///
+/// ```
+/// use ovh::dedicated::server::{IpmiAccessRequest, IpmiAccessType};
+///
+/// let request = IpmiAccessRequest::new(IpmiAccessType::KvmHtml5Url, 15)
+///     .allowed_ip("203.0.113.7".parse().unwrap());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IpmiAccessRequest {
+    #[serde(rename = "type")]
+    access_type: IpmiAccessType,
+    ttl: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ip_to_allow: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    ssh_key: Option<String>,
+}
+
+impl IpmiAccessRequest {
+    /// Requests an access valid for `ttl` minutes, from 1 to 15.
+    pub fn new(access_type: IpmiAccessType, ttl: u32) -> Self {
+        IpmiAccessRequest {
+            access_type,
+            ttl,
+            ip_to_allow: None,
+            ssh_key: None,
+        }
+    }
+
+    /// Only allows the access from the given address.
+    pub fn allowed_ip(mut self, ip: IpAddr) -> Self {
+        self.ip_to_allow = Some(ip);
+        self
+    }
+
+    /// Public SSH key allowed to use a serial console over SSH.
+    pub fn ssh_key(mut self, key: &str) -> Self {
+        self.ssh_key = Some(key.into());
+        self
+    }
+}
+
+/// Temporary access to the IPMI of a server.
+#[derive(Debug, Clone, Deserialize)]
+pub struct IpmiAccess {
+    /// URL, Java Web Start file or SSH command, depending on the kind of
+    /// access.
+    pub value: String,
+    pub expiration: String,
+}
+
+fn server_path(server: &str) -> String {
+    format!("/dedicated/server/{}", encode_path_segment(server))
+}
+
+/// Lists the names of the dedicated servers of the account.
+pub async fn list_servers(client: &OvhClient) -> Result<Vec<String>> {
+    client.get_json("/dedicated/server").await
+}
+
+/// Retrieves a server.
+pub async fn get_server(client: &OvhClient, server: &str) -> Result<Server> {
+    client.get_json(&server_path(server)).await
+}
+
+/// Updates a server.
+pub async fn update_server(client: &OvhClient, server: &str, update: &ServerUpdate) -> Result<()> {
+    client.put_json(&server_path(server), update).await
+}
+
+/// Retrieves the hardware specifications of a server.
+pub async fn hardware_specifications(
+    client: &OvhClient,
+    server: &str,
+) -> Result<HardwareSpecifications> {
+    let path = format!("{}/specifications/hardware", server_path(server));
+    client.get_json(&path).await
+}
+
+/// Hard reboots a server.
+pub async fn reboot(client: &OvhClient, server: &str) -> Result<Task> {
+    let path = format!("{}/reboot", server_path(server));
+    client.request(Method::POST, &path).send_json().await
+}
+
+/// Lists the IDs of the netboots of a server, optionally only those of
+/// the given type.
+pub async fn list_boots(
+    client: &OvhClient,
+    server: &str,
+    boot_type: Option<BootType>,
+) -> Result<Vec<u64>> {
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Filter {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        boot_type: Option<BootType>,
+    }
+
+    let path = format!("{}/boot", server_path(server));
+    client
+        .get_json_with_query(&path, &Filter { boot_type })
+        .await
+}
+
+/// Retrieves a netboot of a server.
+pub async fn get_boot(client: &OvhClient, server: &str, id: u64) -> Result<Boot> {
+    let path = format!("{}/boot/{}", server_path(server), id);
+    client.get_json(&path).await
+}
+
+/// Sets the netboot a server starts on from its next reboot.
+pub async fn set_boot(client: &OvhClient, server: &str, id: u64) -> Result<()> {
+    let update = ServerUpdate {
+        boot_id: Some(id),
+        ..Default::default()
+    };
+    update_server(client, server, &update).await
+}
+
+/// Sets a server to start on the first netboot of the given type from its
+/// next reboot, e.g. [`BootType::Rescue`] for the rescue mode and
+/// [`BootType::Harddisk`] to go back to the installed system.
+///
+/// Returns the ID of the chosen netboot; use [`set_boot`] to choose
+/// among several netboots of the same type.
+pub async fn set_boot_type(client: &OvhClient, server: &str, boot_type: BootType) -> Result<u64> {
+    let ids = list_boots(client, server, Some(boot_type)).await?;
+    let id = *ids.first().ok_or_else(|| {
+        Error::ArgumentError(format!("no {:?} netboot for server `{}`", boot_type, server))
+    })?;
+    set_boot(client, server, id).await?;
+    Ok(id)
+}
+
+/// Lists the installation templates compatible with a server.
+pub async fn compatible_templates(client: &OvhClient, server: &str) -> Result<CompatibleTemplates> {
+    let path = format!("{}/install/compatibleTemplates", server_path(server));
+    client.get_json(&path).await
+}
+
+/// Lists the partition schemes of an installation template provided by
+/// OVH.
+pub async fn list_partition_schemes(client: &OvhClient, template: &str) -> Result<Vec<String>> {
+    let path = format!(
+        "/dedicated/installationTemplate/{}/partitionScheme",
+        encode_path_segment(template)
+    );
+    client.get_json(&path).await
+}
+
+/// Lists the partition schemes of an installation template of the
+/// account.
+pub async fn list_personal_partition_schemes(
+    client: &OvhClient,
+    template: &str,
+) -> Result<Vec<String>> {
+    let path = format!(
+        "/me/installationTemplate/{}/partitionScheme",
+        encode_path_segment(template)
+    );
+    client.get_json(&path).await
+}
+
+/// Reinstalls a server, erasing its disks.
+pub async fn install(client: &OvhClient, server: &str, install: &Install) -> Result<Task> {
+    let path = format!("{}/install/start", server_path(server));
+    client.post_json(&path, install).await
+}
+
+/// Retrieves the progress of the running installation of a server.
+pub async fn install_status(client: &OvhClient, server: &str) -> Result<InstallStatus> {
+    let path = format!("{}/install/status", server_path(server));
+    client.get_json(&path).await
+}
+
+/// Retrieves the IPMI of a server.
+pub async fn ipmi(client: &OvhClient, server: &str) -> Result<Ipmi> {
+    let path = format!("{}/features/ipmi", server_path(server));
+    client.get_json(&path).await
+}
+
+/// Requests a temporary access to the IPMI of a server.
+///
+/// The access can be retrieved with [`ipmi_access`] once the returned
+/// task is done.
+pub async fn request_ipmi_access(
+    client: &OvhClient,
+    server: &str,
+    request: &IpmiAccessRequest,
+) -> Result<Task> {
+    let path = format!("{}/features/ipmi/access", server_path(server));
+    client.post_json(&path, request).await
+}
+
+/// Retrieves a temporary access to the IPMI of a server.
+pub async fn ipmi_access(
+    client: &OvhClient,
+    server: &str,
+    access_type: IpmiAccessType,
+) -> Result<IpmiAccess> {
+    #[derive(Serialize)]
+    struct Query {
+        #[serde(rename = "type")]
+        access_type: IpmiAccessType,
+    }
+
+    let path = format!("{}/features/ipmi/access", server_path(server));
+    client
+        .get_json_with_query(&path, &Query { access_type })
+        .await
+}
+
+/// Lists the IDs of the tasks of a server.
+pub async fn list_tasks(client: &OvhClient, server: &str) -> Result<Vec<u64>> {
+    let path = format!("{}/task", server_path(server));
+    client.get_json(&path).await
+}
+
+/// Retrieves a task of a server.
+pub async fn get_task(client: &OvhClient, server: &str, id: u64) -> Result<Task> {
+    let path = format!("{}/task/{}", server_path(server), id);
+    client.get_json(&path).await
+}
+
+/// Waits for a task of a server to complete.
+pub async fn wait_for_task(
+    client: &OvhClient,
+    server: &str,
+    id: u64,
+    options: &WaitOptions,
+) -> Result<TaskOutcome> {
+    let path = format!("{}/task/{}", server_path(server), id);
+    client.wait_for_task(&path, options).await
+}
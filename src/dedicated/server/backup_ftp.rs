@@ -0,0 +1,170 @@
+//! Backup storage of a server, reachable over FTP, NFS and CIFS from the
+//! allowed IP blocks.
+//!
+//! ```no_run
+//! # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+//! use ovh::dedicated::server::backup_ftp::{self, NewAccess};
+//!
+//! let name = "ns1234567.ip-203-0-113.eu";
+//! let backup = backup_ftp::get_backup_ftp(&client, name).await?;
+//! println!("{} {} used of {}", backup.ftp_backup_name, backup.usage.value, backup.quota.value);
+//!
+//! let access = NewAccess::new("203.0.113.10/32").ftp(true).nfs(true);
+//! backup_ftp::create_access(&client, name, &access).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{server_path, Size};
+use crate::client::{encode_path_segment, Method, OvhClient, Result, Task};
+use serde::{Deserialize, Serialize};
+
+/// Backup storage of a server.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupFtp {
+    /// Host name of the storage.
+    pub ftp_backup_name: String,
+    pub quota: Size,
+    pub usage: Size,
+    #[serde(rename = "type")]
+    pub backup_type: String,
+    /// Date the storage became read-only, after its quota was exceeded.
+    pub read_only_date: Option<String>,
+}
+
+/// Protocols an IP block may use to reach the backup storage.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Access {
+    pub ip_block: String,
+    pub ftp: bool,
+    pub nfs: bool,
+    pub cifs: bool,
+    /// Whether the access is effective yet.
+    pub is_applied: bool,
+    pub last_update: Option<String>,
+}
+
+/// Access to create, every protocol being denied by default.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewAccess {
+    ip_block: String,
+    ftp: bool,
+    nfs: bool,
+    cifs: bool,
+}
+
+impl NewAccess {
+    /// Access for an IP block allowed by [`authorizable_blocks`].
+    pub fn new(ip_block: &str) -> Self {
+        NewAccess {
+            ip_block: ip_block.into(),
+            ftp: false,
+            nfs: false,
+            cifs: false,
+        }
+    }
+
+    pub fn ftp(mut self, allowed: bool) -> Self {
+        self.ftp = allowed;
+        self
+    }
+
+    pub fn nfs(mut self, allowed: bool) -> Self {
+        self.nfs = allowed;
+        self
+    }
+
+    pub fn cifs(mut self, allowed: bool) -> Self {
+        self.cifs = allowed;
+        self
+    }
+}
+
+/// Changes to apply to an access, unset fields being kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ftp: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nfs: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cifs: Option<bool>,
+}
+
+fn backup_ftp_path(server: &str) -> String {
+    format!("{}/features/backupFTP", server_path(server))
+}
+
+fn access_path(server: &str, ip_block: &str) -> String {
+    format!(
+        "{}/access/{}",
+        backup_ftp_path(server),
+        encode_path_segment(ip_block)
+    )
+}
+
+/// Retrieves the backup storage of a server, with its quota and usage.
+pub async fn get_backup_ftp(client: &OvhClient, server: &str) -> Result<BackupFtp> {
+    client.get_json(&backup_ftp_path(server)).await
+}
+
+/// Activates the backup storage of a server.
+pub async fn activate(client: &OvhClient, server: &str) -> Result<Task> {
+    client
+        .request(Method::POST, &backup_ftp_path(server))
+        .send_json()
+        .await
+}
+
+/// Terminates the backup storage of a server, erasing its content.
+pub async fn terminate(client: &OvhClient, server: &str) -> Result<Task> {
+    client.delete_json(&backup_ftp_path(server)).await
+}
+
+/// Generates a new password for the backup storage, sent by email.
+pub async fn reset_password(client: &OvhClient, server: &str) -> Result<Task> {
+    let path = format!("{}/password", backup_ftp_path(server));
+    client.request(Method::POST, &path).send_json().await
+}
+
+/// Lists the IP blocks which may be allowed to reach the backup storage.
+pub async fn authorizable_blocks(client: &OvhClient, server: &str) -> Result<Vec<String>> {
+    let path = format!("{}/authorizableBlocks", backup_ftp_path(server));
+    client.get_json(&path).await
+}
+
+/// Lists the IP blocks allowed to reach the backup storage.
+pub async fn list_accesses(client: &OvhClient, server: &str) -> Result<Vec<String>> {
+    let path = format!("{}/access", backup_ftp_path(server));
+    client.get_json(&path).await
+}
+
+/// Retrieves the access of an IP block to the backup storage.
+pub async fn get_access(client: &OvhClient, server: &str, ip_block: &str) -> Result<Access> {
+    client.get_json(&access_path(server, ip_block)).await
+}
+
+/// Allows an IP block to reach the backup storage.
+pub async fn create_access(client: &OvhClient, server: &str, access: &NewAccess) -> Result<Task> {
+    let path = format!("{}/access", backup_ftp_path(server));
+    client.post_json(&path, access).await
+}
+
+/// Updates the access of an IP block to the backup storage.
+pub async fn update_access(
+    client: &OvhClient,
+    server: &str,
+    ip_block: &str,
+    update: &AccessUpdate,
+) -> Result<()> {
+    client.put_json(&access_path(server, ip_block), update).await
+}
+
+/// Denies an IP block access to the backup storage.
+pub async fn delete_access(client: &OvhClient, server: &str, ip_block: &str) -> Result<Task> {
+    client.delete_json(&access_path(server, ip_block)).await
+}
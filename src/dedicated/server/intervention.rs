@@ -0,0 +1,27 @@
+//! Hardware interventions of OVH technicians on a server.
+
+use super::server_path;
+use crate::client::{OvhClient, Result};
+use serde::Deserialize;
+
+/// Intervention on a server, such as a disk replacement.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Intervention {
+    pub intervention_id: u64,
+    pub date: String,
+    #[serde(rename = "type")]
+    pub intervention_type: Option<String>,
+}
+
+/// Lists the IDs of the past interventions on a server.
+pub async fn list_interventions(client: &OvhClient, server: &str) -> Result<Vec<u64>> {
+    let path = format!("{}/intervention", server_path(server));
+    client.get_json(&path).await
+}
+
+/// Retrieves an intervention on a server.
+pub async fn get_intervention(client: &OvhClient, server: &str, id: u64) -> Result<Intervention> {
+    let path = format!("{}/intervention/{}", server_path(server), id);
+    client.get_json(&path).await
+}
@@ -0,0 +1,252 @@
+//! Monitoring of the services of a server by OVH.
+//!
+//! Ping monitoring of the server itself is toggled with
+//! [`set_ping_monitoring`]; services listening on the server are checked
+//! by service monitorings.
+//!
+//! ```no_run
+//! # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+//! use ovh::dedicated::server::monitoring::{
+//!     self, MonitoringInterval, NewServiceMonitoring, Protocol,
+//! };
+//!
+//! let name = "ns1234567.ip-203-0-113.eu";
+//! let ip = "203.0.113.10".parse().unwrap();
+//! let monitoring = NewServiceMonitoring::new(Protocol::Http, ip, 80)
+//!     .url("/health")
+//!     .challenge_text("ok")
+//!     .interval(MonitoringInterval::FiveMinutes);
+//! monitoring::create_service_monitoring(&client, name, &monitoring).await?;
+//! monitoring::set_ping_monitoring(&client, name, true).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{server_path, update_server, ServerUpdate};
+use crate::client::{OvhClient, Result};
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+/// Protocol of a monitored service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Protocol {
+    #[serde(rename = "DNS")]
+    Dns,
+    #[serde(rename = "FTP")]
+    Ftp,
+    #[serde(rename = "HTTP")]
+    Http,
+    #[serde(rename = "IMAP")]
+    Imap,
+    #[serde(rename = "POP")]
+    Pop,
+    #[serde(rename = "SMTP")]
+    Smtp,
+    #[serde(rename = "SSH")]
+    Ssh,
+    /// Any service accepting TCP connections.
+    #[serde(rename = "openTCP")]
+    OpenTcp,
+}
+
+/// Time between two checks of a service.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MonitoringInterval {
+    #[serde(rename = "300")]
+    FiveMinutes,
+    #[serde(rename = "1800")]
+    ThirtyMinutes,
+    #[serde(rename = "3600")]
+    OneHour,
+    #[serde(rename = "21600")]
+    SixHours,
+}
+
+/// Monitoring of a service of a server.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceMonitoring {
+    pub monitoring_id: u64,
+    pub protocol: Protocol,
+    pub ip: IpAddr,
+    pub port: u16,
+    pub url: Option<String>,
+    pub challenge_text: Option<String>,
+    pub interval: MonitoringInterval,
+    pub enabled: bool,
+}
+
+/// Monitoring to create.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewServiceMonitoring {
+    protocol: Protocol,
+    ip: IpAddr,
+    port: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    challenge_text: Option<String>,
+    interval: MonitoringInterval,
+}
+
+impl NewServiceMonitoring {
+    /// Checks the service at `ip` and `port` every hour.
+    pub fn new(protocol: Protocol, ip: IpAddr, port: u16) -> Self {
+        NewServiceMonitoring {
+            protocol,
+            ip,
+            port,
+            url: None,
+            challenge_text: None,
+            interval: MonitoringInterval::OneHour,
+        }
+    }
+
+    /// Checks the given URL, for HTTP services.
+    pub fn url(mut self, url: &str) -> Self {
+        self.url = Some(url.into());
+        self
+    }
+
+    /// Expects the answer of the service to contain the given text.
+    pub fn challenge_text(mut self, text: &str) -> Self {
+        self.challenge_text = Some(text.into());
+        self
+    }
+
+    pub fn interval(mut self, interval: MonitoringInterval) -> Self {
+        self.interval = interval;
+        self
+    }
+}
+
+/// Changes to apply to a monitoring, unset fields being kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceMonitoringUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip: Option<IpAddr>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub challenge_text: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub interval: Option<MonitoringInterval>,
+}
+
+/// Email alert sent when a monitored service fails.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EmailAlert {
+    pub alert_id: u64,
+    pub email: String,
+    pub language: String,
+    pub enabled: bool,
+}
+
+fn monitoring_path(server: &str) -> String {
+    format!("{}/serviceMonitoring", server_path(server))
+}
+
+/// Enables or disables the ping monitoring of a server, OVH intervening
+/// when the server stops answering.
+pub async fn set_ping_monitoring(client: &OvhClient, server: &str, enabled: bool) -> Result<()> {
+    let update = ServerUpdate {
+        monitoring: Some(enabled),
+        ..Default::default()
+    };
+    update_server(client, server, &update).await
+}
+
+/// Lists the IDs of the service monitorings of a server.
+pub async fn list_service_monitorings(client: &OvhClient, server: &str) -> Result<Vec<u64>> {
+    client.get_json(&monitoring_path(server)).await
+}
+
+/// Retrieves a service monitoring of a server.
+pub async fn get_service_monitoring(
+    client: &OvhClient,
+    server: &str,
+    id: u64,
+) -> Result<ServiceMonitoring> {
+    let path = format!("{}/{}", monitoring_path(server), id);
+    client.get_json(&path).await
+}
+
+/// Creates a service monitoring on a server.
+pub async fn create_service_monitoring(
+    client: &OvhClient,
+    server: &str,
+    monitoring: &NewServiceMonitoring,
+) -> Result<ServiceMonitoring> {
+    client.post_json(&monitoring_path(server), monitoring).await
+}
+
+/// Updates a service monitoring of a server.
+pub async fn update_service_monitoring(
+    client: &OvhClient,
+    server: &str,
+    id: u64,
+    update: &ServiceMonitoringUpdate,
+) -> Result<()> {
+    let path = format!("{}/{}", monitoring_path(server), id);
+    client.put_json(&path, update).await
+}
+
+/// Deletes a service monitoring of a server.
+pub async fn delete_service_monitoring(client: &OvhClient, server: &str, id: u64) -> Result<()> {
+    let path = format!("{}/{}", monitoring_path(server), id);
+    client.delete_json(&path).await
+}
+
+/// Lists the IDs of the email alerts of a service monitoring.
+pub async fn list_email_alerts(client: &OvhClient, server: &str, id: u64) -> Result<Vec<u64>> {
+    let path = format!("{}/{}/alert/email", monitoring_path(server), id);
+    client.get_json(&path).await
+}
+
+/// Retrieves an email alert of a service monitoring.
+pub async fn get_email_alert(
+    client: &OvhClient,
+    server: &str,
+    id: u64,
+    alert_id: u64,
+) -> Result<EmailAlert> {
+    let path = format!("{}/{}/alert/email/{}", monitoring_path(server), id, alert_id);
+    client.get_json(&path).await
+}
+
+/// Sends an email to `email` when a monitored service fails, `language`
+/// being e.g. `en` or `fr`.
+pub async fn create_email_alert(
+    client: &OvhClient,
+    server: &str,
+    id: u64,
+    email: &str,
+    language: &str,
+) -> Result<EmailAlert> {
+    #[derive(Serialize)]
+    struct Body<'a> {
+        email: &'a str,
+        language: &'a str,
+    }
+
+    let path = format!("{}/{}/alert/email", monitoring_path(server), id);
+    client.post_json(&path, &Body { email, language }).await
+}
+
+/// Deletes an email alert of a service monitoring.
+pub async fn delete_email_alert(
+    client: &OvhClient,
+    server: &str,
+    id: u64,
+    alert_id: u64,
+) -> Result<()> {
+    let path = format!("{}/{}/alert/email/{}", monitoring_path(server), id, alert_id);
+    client.delete_json(&path).await
+}
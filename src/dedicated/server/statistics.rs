@@ -0,0 +1,155 @@
+//! Statistics reported by the real time monitoring (RTM) agent installed
+//! on a server.
+//!
+//! ```no_run
+//! # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+//! use ovh::dedicated::server::statistics::{self, ChartPeriod, ChartType};
+//!
+//! let name = "ns1234567.ip-203-0-113.eu";
+//! let chart = statistics::chart(&client, name, ChartType::Cpu, ChartPeriod::Daily).await?;
+//! for point in chart.values {
+//!     println!("{} {:?} {}", point.timestamp, point.value, chart.unit);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use super::{server_path, Size};
+use crate::client::{OvhClient, Result};
+use serde::{Deserialize, Serialize};
+
+/// Metric of a chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChartType {
+    Cpu,
+    #[serde(rename = "loadavg1")]
+    LoadAverage1,
+    #[serde(rename = "loadavg5")]
+    LoadAverage5,
+    #[serde(rename = "loadavg15")]
+    LoadAverage15,
+    Memory,
+    ProcessCount,
+    ProcessRunning,
+    Swap,
+}
+
+/// Time span of a chart.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ChartPeriod {
+    Daily,
+    Hourly,
+    Monthly,
+    Weekly,
+    Yearly,
+}
+
+/// Value of a metric at a given time.
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct ChartPoint {
+    /// UNIX timestamp, in seconds.
+    pub timestamp: i64,
+    /// Value, unset if none was reported.
+    pub value: Option<f64>,
+}
+
+/// Values of a metric over time.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Chart {
+    pub unit: String,
+    pub values: Vec<ChartPoint>,
+}
+
+/// Processor of a server.
+#[derive(Debug, Clone, Deserialize)]
+pub struct Cpu {
+    pub name: String,
+    pub core: u32,
+    pub freq: Size,
+    pub cache: Size,
+}
+
+/// Memory module of a server.
+#[derive(Debug, Clone, Deserialize)]
+pub struct MemoryModule {
+    pub slot: String,
+    pub capacity: Size,
+}
+
+/// Current load of a server.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Load {
+    pub cpu: Size,
+    pub memory: Size,
+    pub swap: Size,
+    #[serde(rename = "loadavg1")]
+    pub load_average_1: f64,
+    #[serde(rename = "loadavg5")]
+    pub load_average_5: f64,
+    #[serde(rename = "loadavg15")]
+    pub load_average_15: f64,
+    pub process_count: u64,
+    pub process_running: u64,
+    /// Uptime, in seconds.
+    pub uptime: u64,
+}
+
+/// Operating system of a server.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Os {
+    pub release: String,
+    pub kernel_release: String,
+    pub kernel_version: String,
+}
+
+fn statistics_path(server: &str) -> String {
+    format!("{}/statistics", server_path(server))
+}
+
+/// Retrieves the values of a metric of a server over a period.
+pub async fn chart(
+    client: &OvhClient,
+    server: &str,
+    chart_type: ChartType,
+    period: ChartPeriod,
+) -> Result<Chart> {
+    #[derive(Serialize)]
+    struct Query {
+        #[serde(rename = "type")]
+        chart_type: ChartType,
+        period: ChartPeriod,
+    }
+
+    let path = format!("{}/chart", statistics_path(server));
+    client
+        .get_json_with_query(&path, &Query { chart_type, period })
+        .await
+}
+
+/// Retrieves the processor of a server.
+pub async fn cpu(client: &OvhClient, server: &str) -> Result<Cpu> {
+    let path = format!("{}/cpu", statistics_path(server));
+    client.get_json(&path).await
+}
+
+/// Retrieves the memory modules of a server.
+pub async fn memory(client: &OvhClient, server: &str) -> Result<Vec<MemoryModule>> {
+    let path = format!("{}/memory", statistics_path(server));
+    client.get_json(&path).await
+}
+
+/// Retrieves the current load of a server.
+pub async fn load(client: &OvhClient, server: &str) -> Result<Load> {
+    let path = format!("{}/load", statistics_path(server));
+    client.get_json(&path).await
+}
+
+/// Retrieves the operating system of a server.
+pub async fn os(client: &OvhClient, server: &str) -> Result<Os> {
+    let path = format!("{}/os", statistics_path(server));
+    client.get_json(&path).await
+}
@@ -0,0 +1,323 @@
+//! Domain names and their DNS zones.
+//!
+//! Functions of this module manage the registration of domain names,
+//! under `/domain/{domain}`.
+
+use crate::client::{encode_path_segment, OvhClient, Result, Task, TaskOutcome, WaitOptions};
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+pub mod acme;
+pub mod dynhost;
+pub mod zone;
+
+/// Kind of the name servers of a domain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum NameServerType {
+    /// Name servers hosted outside of OVH.
+    External,
+    /// OVH name servers.
+    Hosted,
+}
+
+/// Protection of a domain against transfers to another registrar.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TransferLockStatus {
+    Locked,
+    Locking,
+    Unavailable,
+    Unlocked,
+    Unlocking,
+}
+
+/// Registered domain name.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Domain {
+    pub domain: String,
+    pub name_server_type: NameServerType,
+    pub transfer_lock_status: TransferLockStatus,
+    pub offer: String,
+    pub owo_supported: bool,
+    pub dnssec_supported: bool,
+    pub glue_record_ipv6_supported: bool,
+    pub glue_record_multi_ip_supported: bool,
+    pub whois_owner: String,
+    pub last_update: String,
+}
+
+/// Renewal settings of a service.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Renew {
+    pub automatic: bool,
+    pub delete_at_expiration: bool,
+    pub forced: bool,
+    pub manual_payment: Option<bool>,
+    /// Renewal period, in months.
+    pub period: Option<u32>,
+}
+
+/// Billing information and contacts of a domain.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceInfos {
+    pub domain: String,
+    pub service_id: u64,
+    pub status: String,
+    pub creation: String,
+    pub expiration: String,
+    pub engaged_up_to: Option<String>,
+    pub renewal_type: String,
+    pub renew: Option<Renew>,
+    /// NIC handle of the administrative contact.
+    pub contact_admin: String,
+    /// NIC handle of the billing contact.
+    pub contact_billing: String,
+    /// NIC handle of the technical contact.
+    pub contact_tech: String,
+}
+
+/// Name server of a domain.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NameServer {
+    pub id: u64,
+    pub host: String,
+    /// Glue record address, for name servers inside the domain itself.
+    pub ip: Option<IpAddr>,
+    pub is_used: bool,
+    pub to_delete: bool,
+}
+
+/// Name server to set on a domain.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct NewNameServer {
+    pub host: String,
+    /// Glue record address, for name servers inside the domain itself.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip: Option<IpAddr>,
+}
+
+impl NewNameServer {
+    /// Name server without glue record.
+    pub fn new(host: &str) -> Self {
+        NewNameServer {
+            host: host.into(),
+            ip: None,
+        }
+    }
+
+    /// Sets the glue record address of the name server.
+    pub fn ip(mut self, ip: IpAddr) -> Self {
+        self.ip = Some(ip);
+        self
+    }
+}
+
+/// DS record published in the parent zone of a domain, for DNSSEC.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DsRecord {
+    pub id: u64,
+    pub algorithm: u8,
+    pub flags: u16,
+    pub tag: u16,
+    pub public_key: String,
+    pub status: String,
+}
+
+/// DNSSEC key to publish as a DS record.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DnssecKey {
+    pub algorithm: u8,
+    /// 257 for a key signing key, 256 for a zone signing key.
+    pub flags: u16,
+    pub tag: u16,
+    pub public_key: String,
+}
+
+/// Whois field hidden by the obfuscator (OWO).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OwoField {
+    Address,
+    Email,
+    Phone,
+}
+
+impl OwoField {
+    fn as_str(self) -> &'static str {
+        match self {
+            OwoField::Address => "address",
+            OwoField::Email => "email",
+            OwoField::Phone => "phone",
+        }
+    }
+}
+
+fn domain_path(domain: &str) -> String {
+    format!("/domain/{}", encode_path_segment(domain))
+}
+
+/// Lists the domain names of the account.
+pub async fn list_domains(client: &OvhClient) -> Result<Vec<String>> {
+    client.get_json("/domain").await
+}
+
+/// Retrieves a domain name.
+pub async fn get_domain(client: &OvhClient, domain: &str) -> Result<Domain> {
+    client.get_json(&domain_path(domain)).await
+}
+
+/// Retrieves the billing information and contacts of a domain.
+pub async fn service_infos(client: &OvhClient, domain: &str) -> Result<ServiceInfos> {
+    let path = format!("{}/serviceInfos", domain_path(domain));
+    client.get_json(&path).await
+}
+
+/// Retrieves an asynchronous operation on a domain, such as a name
+/// servers update.
+pub async fn get_task(client: &OvhClient, domain: &str, id: u64) -> Result<Task> {
+    let path = format!("{}/task/{}", domain_path(domain), id);
+    client.get_json(&path).await
+}
+
+/// Waits for an asynchronous operation on a domain to complete.
+pub async fn wait_for_task(
+    client: &OvhClient,
+    domain: &str,
+    id: u64,
+    options: &WaitOptions,
+) -> Result<TaskOutcome> {
+    let path = format!("{}/task/{}", domain_path(domain), id);
+    client.wait_for_task(&path, options).await
+}
+
+/// Lists the IDs of the name servers of a domain.
+pub async fn list_name_servers(client: &OvhClient, domain: &str) -> Result<Vec<u64>> {
+    let path = format!("{}/nameServer", domain_path(domain));
+    client.get_json(&path).await
+}
+
+/// Retrieves a name server of a domain.
+pub async fn get_name_server(client: &OvhClient, domain: &str, id: u64) -> Result<NameServer> {
+    let path = format!("{}/nameServer/{}", domain_path(domain), id);
+    client.get_json(&path).await
+}
+
+/// Replaces the name servers of a domain.
+///
+/// ```no_run
+/// # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+/// use ovh::{
+///     client::WaitOptions,
+///     domain::{self, NewNameServer},
+/// };
+///
+/// let name_servers = [
+///     NewNameServer::new("ns1.example.net"),
+///     NewNameServer::new("ns2.example.net"),
+/// ];
+/// let task = domain::update_name_servers(&client, "example.com", &name_servers).await?;
+/// let outcome =
+///     domain::wait_for_task(&client, "example.com", task.id, &WaitOptions::new()).await?;
+/// assert!(outcome.is_done());
+/// # Ok(())
+/// # }
+/// ```
+pub async fn update_name_servers(
+    client: &OvhClient,
+    domain: &str,
+    name_servers: &[NewNameServer],
+) -> Result<Task> {
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Body<'a> {
+        name_servers: &'a [NewNameServer],
+    }
+
+    let path = format!("{}/nameServers/update", domain_path(domain));
+    client.post_json(&path, &Body { name_servers }).await
+}
+
+/// Lists the IDs of the DS records of a domain.
+pub async fn list_ds_records(client: &OvhClient, domain: &str) -> Result<Vec<u64>> {
+    let path = format!("{}/dsRecord", domain_path(domain));
+    client.get_json(&path).await
+}
+
+/// Retrieves a DS record of a domain.
+pub async fn get_ds_record(client: &OvhClient, domain: &str, id: u64) -> Result<DsRecord> {
+    let path = format!("{}/dsRecord/{}", domain_path(domain), id);
+    client.get_json(&path).await
+}
+
+/// Replaces the DS records of a domain, an empty list disabling DNSSEC.
+pub async fn update_ds_records(
+    client: &OvhClient,
+    domain: &str,
+    keys: &[DnssecKey],
+) -> Result<Task> {
+    #[derive(Serialize)]
+    struct Body<'a> {
+        keys: &'a [DnssecKey],
+    }
+
+    let path = format!("{}/dsRecord", domain_path(domain));
+    client.post_json(&path, &Body { keys }).await
+}
+
+/// Locks or unlocks the transfer of a domain to another registrar.
+pub async fn set_transfer_lock(client: &OvhClient, domain: &str, locked: bool) -> Result<()> {
+    #[derive(Serialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Body {
+        transfer_lock_status: TransferLockStatus,
+    }
+
+    let transfer_lock_status = if locked {
+        TransferLockStatus::Locked
+    } else {
+        TransferLockStatus::Unlocked
+    };
+    client
+        .put_json(&domain_path(domain), &Body { transfer_lock_status })
+        .await
+}
+
+/// Lists the whois fields hidden by the obfuscator.
+pub async fn list_owo_fields(client: &OvhClient, domain: &str) -> Result<Vec<OwoField>> {
+    let path = format!("{}/owo", domain_path(domain));
+    client.get_json(&path).await
+}
+
+/// Hides whois fields, returning the newly hidden ones.
+pub async fn hide_owo_fields(
+    client: &OvhClient,
+    domain: &str,
+    fields: &[OwoField],
+) -> Result<Vec<OwoField>> {
+    #[derive(Serialize)]
+    struct Body<'a> {
+        fields: &'a [OwoField],
+    }
+
+    let path = format!("{}/owo", domain_path(domain));
+    client.post_json(&path, &Body { fields }).await
+}
+
+/// Shows a whois field again.
+pub async fn show_owo_field(client: &OvhClient, domain: &str, field: OwoField) -> Result<()> {
+    let path = format!("{}/owo/{}", domain_path(domain), field.as_str());
+    client.delete_json(&path).await
+}
+
+/// Retrieves the code needed to transfer a domain to another registrar.
+pub async fn auth_info(client: &OvhClient, domain: &str) -> Result<String> {
+    let path = format!("{}/authInfo", domain_path(domain));
+    client.get_json(&path).await
+}
@@ -0,0 +1,647 @@
+//! ACME DNS-01 challenges on zones hosted by OVH.
+//!
+//! The TXT record of a challenge is created in the zone, which is then
+//! refreshed, and the record is waited for on the authoritative name
+//! servers of the zone before the ACME server is asked to validate it.
+//!
+//! ```no_run
+//! # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+//! use ovh::domain::acme::Dns01Solver;
+//!
+//! let solver = Dns01Solver::new();
+//! let challenge = solver
+//!     .present(&client, "example.com", "*.example.com", "key-authorization-digest")
+//!     .await?;
+//! // Ask the ACME server to validate the challenge.
+//! solver.cleanup(&client, &challenge).await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{
+    client::{random_u64, Error, OvhClient, Result},
+    domain::zone::{
+        create_record, delete_record, get_zone, refresh, NewRecord, Record, RecordType,
+    },
+};
+use futures::future::BoxFuture;
+use std::{io, net::SocketAddr, time::Duration};
+use tokio::{
+    net::{lookup_host, UdpSocket},
+    time::{sleep, timeout, Instant},
+};
+
+const CHALLENGE_LABEL: &str = "_acme-challenge";
+
+/// Looks up TXT records on a given name server.
+///
+/// [`UdpResolver`] is used by default; other implementations allow
+/// checking propagation differently, or not at all in tests.
+pub trait TxtResolver: Send + Sync {
+    /// Returns the TXT records of `name` as answered by `server`, each
+    /// record being the concatenation of its strings.
+    fn lookup_txt<'a>(
+        &'a self,
+        server: &'a str,
+        name: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<String>>>;
+}
+
+/// Minimal resolver sending non-recursive queries over UDP.
+#[derive(Debug, Clone)]
+pub struct UdpResolver {
+    port: u16,
+    timeout: Duration,
+    attempts: u32,
+}
+
+impl Default for UdpResolver {
+    fn default() -> Self {
+        UdpResolver {
+            port: 53,
+            timeout: Duration::from_secs(2),
+            attempts: 3,
+        }
+    }
+}
+
+impl UdpResolver {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Port the name servers are queried on, 53 by default.
+    pub fn port(mut self, port: u16) -> Self {
+        self.port = port;
+        self
+    }
+
+    /// Time waited for each answer, 2 seconds by default, [`Duration::MAX`]
+    /// waiting forever.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Number of queries sent before giving up, 3 by default.
+    pub fn attempts(mut self, attempts: u32) -> Self {
+        self.attempts = attempts.max(1);
+        self
+    }
+
+    async fn query(&self, addr: SocketAddr, name: &str) -> Result<Vec<String>> {
+        let bind: SocketAddr = if addr.is_ipv4() {
+            ([0, 0, 0, 0], 0).into()
+        } else {
+            ([0u16; 8], 0).into()
+        };
+        let socket = UdpSocket::bind(bind).await.map_err(io_error)?;
+        socket.connect(addr).await.map_err(io_error)?;
+
+        let id = random_u64() as u16;
+        let query = encode_query(id, name)?;
+        let mut buf = [0; 4096];
+        for _ in 0..self.attempts {
+            socket.send(&query).await.map_err(io_error)?;
+            // A timeout too large to be represented means no deadline.
+            let deadline = Instant::now().checked_add(self.timeout);
+            let remaining = || match deadline {
+                Some(deadline) => deadline.saturating_duration_since(Instant::now()),
+                None => Duration::MAX,
+            };
+            // Answers to other queries are skipped until the deadline.
+            while let Ok(received) = timeout(remaining(), socket.recv(&mut buf)).await {
+                let len = received.map_err(io_error)?;
+                if let Some(records) = decode_answer(id, &buf[..len])? {
+                    return Ok(records);
+                }
+            }
+        }
+
+        Err(io_error(io::Error::new(
+            io::ErrorKind::TimedOut,
+            format!("no answer from {} for {}", addr, name),
+        )))
+    }
+}
+
+impl TxtResolver for UdpResolver {
+    fn lookup_txt<'a>(
+        &'a self,
+        server: &'a str,
+        name: &'a str,
+    ) -> BoxFuture<'a, Result<Vec<String>>> {
+        Box::pin(async move {
+            let addr = lookup_host((server, self.port))
+                .await
+                .map_err(io_error)?
+                .next()
+                .ok_or_else(|| {
+                    io_error(io::Error::new(
+                        io::ErrorKind::NotFound,
+                        format!("no address for {}", server),
+                    ))
+                })?;
+            self.query(addr, name).await
+        })
+    }
+}
+
+fn io_error(e: io::Error) -> Error {
+    Error::Error(e.into())
+}
+
+fn malformed() -> Error {
+    io_error(io::Error::new(
+        io::ErrorKind::InvalidData,
+        "malformed DNS answer",
+    ))
+}
+
+fn encode_query(id: u16, name: &str) -> Result<Vec<u8>> {
+    let mut query = Vec::with_capacity(name.len() + 18);
+    query.extend_from_slice(&id.to_be_bytes());
+    // Standard query, not asking for recursion, with a single question.
+    query.extend_from_slice(&[0, 0, 0, 1, 0, 0, 0, 0, 0, 0]);
+    for label in name.trim_end_matches('.').split('.') {
+        if label.is_empty() || label.len() > 63 {
+            return Err(Error::ArgumentError(format!(
+                "invalid domain name `{}`",
+                name
+            )));
+        }
+        query.push(label.len() as u8);
+        query.extend_from_slice(label.as_bytes());
+    }
+    query.push(0);
+    // Type TXT, class IN.
+    query.extend_from_slice(&[0, 16, 0, 1]);
+    Ok(query)
+}
+
+fn skip_name(msg: &[u8], mut pos: usize) -> Result<usize> {
+    loop {
+        let len = *msg.get(pos).ok_or_else(malformed)? as usize;
+        match len {
+            0 => return Ok(pos + 1),
+            l if l & 0xc0 == 0xc0 => return Ok(pos + 2),
+            l => pos += 1 + l,
+        }
+    }
+}
+
+fn read_u16(msg: &[u8], pos: usize) -> Result<u16> {
+    match msg.get(pos..pos + 2) {
+        Some(b) => Ok(u16::from_be_bytes([b[0], b[1]])),
+        None => Err(malformed()),
+    }
+}
+
+// Returns `None` if the message is not an answer to the query `id`.
+fn decode_answer(id: u16, msg: &[u8]) -> Result<Option<Vec<String>>> {
+    if msg.len() < 12 || read_u16(msg, 0)? != id || msg[2] & 0x80 == 0 {
+        return Ok(None);
+    }
+    match msg[3] & 0x0f {
+        0 => {}
+        // The name does not exist (yet).
+        3 => return Ok(Some(Vec::new())),
+        rcode => {
+            return Err(io_error(io::Error::other(format!("DNS query failed with rcode {}", rcode))))
+        }
+    }
+
+    let questions = read_u16(msg, 4)?;
+    let answers = read_u16(msg, 6)?;
+    let mut pos = 12;
+    for _ in 0..questions {
+        pos = skip_name(msg, pos)? + 4;
+    }
+
+    let mut records = Vec::new();
+    for _ in 0..answers {
+        pos = skip_name(msg, pos)?;
+        let record_type = read_u16(msg, pos)?;
+        let len = read_u16(msg, pos + 8)? as usize;
+        pos += 10;
+        let data = msg.get(pos..pos + len).ok_or_else(malformed)?;
+        pos += len;
+        if record_type != 16 {
+            continue;
+        }
+
+        let mut text = Vec::new();
+        let mut i = 0;
+        while i < data.len() {
+            let l = data[i] as usize;
+            text.extend_from_slice(data.get(i + 1..i + 1 + l).ok_or_else(malformed)?);
+            i += 1 + l;
+        }
+        records.push(String::from_utf8_lossy(&text).into_owned());
+    }
+    Ok(Some(records))
+}
+
+/// Sub-domain of `zone` holding the challenge of `domain`.
+///
+/// Wildcard domains share the challenge of their base domain.
+///
+/// ```
+/// use ovh::domain::acme::challenge_sub_domain;
+///
+/// assert_eq!(
+///     challenge_sub_domain("example.com", "*.example.com").unwrap(),
+///     "_acme-challenge"
+/// );
+/// assert_eq!(
+///     challenge_sub_domain("example.com", "www.example.com.").unwrap(),
+///     "_acme-challenge.www"
+/// );
+/// assert!(challenge_sub_domain("example.com", "example.org").is_err());
+/// ```
+pub fn challenge_sub_domain(zone: &str, domain: &str) -> Result<String> {
+    let zone = zone.trim_end_matches('.').to_ascii_lowercase();
+    let domain = domain.trim_end_matches('.').to_ascii_lowercase();
+    let domain = domain.strip_prefix("*.").unwrap_or(&domain);
+
+    if domain == zone {
+        return Ok(CHALLENGE_LABEL.into());
+    }
+    match domain.strip_suffix(&zone).and_then(|s| s.strip_suffix('.')) {
+        Some(sub_domain) if !sub_domain.is_empty() => {
+            Ok(format!("{}.{}", CHALLENGE_LABEL, sub_domain))
+        }
+        _ => Err(Error::ArgumentError(format!(
+            "`{}` is not in zone `{}`",
+            domain, zone
+        ))),
+    }
+}
+
+/// Challenge record created by [`Dns01Solver::present`].
+#[derive(Debug, Clone)]
+pub struct Challenge {
+    pub zone: String,
+    /// Fully qualified name of the TXT record.
+    pub name: String,
+    pub value: String,
+    pub record: Record,
+}
+
+/// Solver of ACME DNS-01 challenges.
+pub struct Dns01Solver<R = UdpResolver> {
+    resolver: R,
+    ttl: u32,
+    propagation_timeout: Duration,
+    poll_interval: Duration,
+}
+
+impl Default for Dns01Solver {
+    fn default() -> Self {
+        Dns01Solver::with_resolver(UdpResolver::new())
+    }
+}
+
+impl Dns01Solver {
+    pub fn new() -> Self {
+        Default::default()
+    }
+}
+
+impl<R: TxtResolver> Dns01Solver<R> {
+    /// Solver checking propagation with `resolver`.
+    pub fn with_resolver(resolver: R) -> Self {
+        Dns01Solver {
+            resolver,
+            ttl: 60,
+            propagation_timeout: Duration::from_secs(300),
+            poll_interval: Duration::from_secs(5),
+        }
+    }
+
+    /// TTL of the challenge records, 60 seconds by default.
+    pub fn ttl(mut self, ttl: u32) -> Self {
+        self.ttl = ttl;
+        self
+    }
+
+    /// Time waited for the record to be visible, 5 minutes by default,
+    /// [`Duration::MAX`] waiting forever.
+    pub fn propagation_timeout(mut self, timeout: Duration) -> Self {
+        self.propagation_timeout = timeout;
+        self
+    }
+
+    /// Time between propagation checks, 5 seconds by default.
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// Creates the TXT record holding `value` for `domain` in `zone`, then
+    /// waits for it to be visible.
+    ///
+    /// The record is deleted if it does not become visible in time.
+    pub async fn present(
+        &self,
+        client: &OvhClient,
+        zone: &str,
+        domain: &str,
+        value: &str,
+    ) -> Result<Challenge> {
+        let sub_domain = challenge_sub_domain(zone, domain)?;
+        let record =
+            NewRecord::new(RecordType::Txt, &sub_domain, &format!("\"{}\"", value)).ttl(self.ttl);
+        let record = create_record(client, zone, &record).await?;
+
+        let challenge = Challenge {
+            zone: zone.into(),
+            name: format!("{}.{}", sub_domain, zone.trim_end_matches('.')),
+            value: value.into(),
+            record,
+        };
+
+        let result = match refresh(client, zone).await {
+            Ok(()) => self.wait(client, &challenge).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = result {
+            // The original error matters more than a failed cleanup.
+            let _ = self.cleanup(client, &challenge).await;
+            return Err(e);
+        }
+
+        Ok(challenge)
+    }
+
+    /// Waits until all the name servers of the zone answer the challenge
+    /// value.
+    pub async fn wait(&self, client: &OvhClient, challenge: &Challenge) -> Result<()> {
+        let zone = get_zone(client, &challenge.zone).await?;
+        self.wait_on(zone.name_servers, &challenge.name, &challenge.value)
+            .await
+    }
+
+    async fn wait_on(&self, name_servers: Vec<String>, name: &str, value: &str) -> Result<()> {
+        // A timeout too large to be represented means no deadline.
+        let deadline = Instant::now().checked_add(self.propagation_timeout);
+
+        let mut pending = name_servers;
+        loop {
+            let mut still_pending = Vec::new();
+            for server in pending {
+                // Name servers failing to answer are tried again later.
+                match self.resolver.lookup_txt(&server, name).await {
+                    Ok(records) if records.iter().any(|r| r == value) => {}
+                    _ => still_pending.push(server),
+                }
+            }
+            pending = still_pending;
+
+            if pending.is_empty() {
+                return Ok(());
+            }
+            let timed_out = match (deadline, Instant::now().checked_add(self.poll_interval)) {
+                (None, _) => false,
+                (Some(deadline), Some(next_poll)) => next_poll > deadline,
+                (Some(_), None) => true,
+            };
+            if timed_out {
+                return Err(Error::TimeoutError(format!(
+                    "{} not visible on {} after {:?}",
+                    name,
+                    pending.join(", "),
+                    self.propagation_timeout
+                )));
+            }
+            sleep(self.poll_interval).await;
+        }
+    }
+
+    /// Deletes the challenge record and refreshes the zone.
+    pub async fn cleanup(&self, client: &OvhClient, challenge: &Challenge) -> Result<()> {
+        match delete_record(client, &challenge.zone, challenge.record.id).await {
+            Err(Error::ApiError(e)) if e.is_not_found() => {}
+            result => result?,
+        }
+        refresh(client, &challenge.zone).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    // Answer to `query` with the given answer records, each made of a name,
+    // a type and the record data.
+    fn answer(query: &[u8], rcode: u8, records: &[(&[u8], u16, &[u8])]) -> Vec<u8> {
+        let mut msg = query.to_vec();
+        msg[2] = 0x80;
+        msg[3] = rcode;
+        msg[6..8].copy_from_slice(&(records.len() as u16).to_be_bytes());
+        for (name, record_type, data) in records {
+            msg.extend_from_slice(name);
+            msg.extend_from_slice(&record_type.to_be_bytes());
+            // Class IN, TTL of 60 seconds.
+            msg.extend_from_slice(&[0, 1, 0, 0, 0, 60]);
+            msg.extend_from_slice(&(data.len() as u16).to_be_bytes());
+            msg.extend_from_slice(data);
+        }
+        msg
+    }
+
+    // Compressed name pointing to the question.
+    const QUESTION_NAME: &[u8] = &[0xc0, 12];
+
+    #[test]
+    fn decodes_compressed_names() {
+        let query = encode_query(42, "_acme-challenge.example.com").unwrap();
+        let msg = answer(
+            &query,
+            0,
+            &[
+                (QUESTION_NAME, 16, b"\x03abc"),
+                // A label followed by a pointer.
+                (b"\x03www\xc0\x0c", 16, b"\x03def"),
+            ],
+        );
+        assert_eq!(
+            decode_answer(42, &msg).unwrap(),
+            Some(vec!["abc".to_string(), "def".to_string()])
+        );
+    }
+
+    #[test]
+    fn concatenates_strings() {
+        let query = encode_query(42, "_acme-challenge.example.com").unwrap();
+        let msg = answer(&query, 0, &[(QUESTION_NAME, 16, b"\x03abc\x00\x03def")]);
+        assert_eq!(decode_answer(42, &msg).unwrap(), Some(vec!["abcdef".to_string()]));
+    }
+
+    #[test]
+    fn skips_other_types() {
+        let query = encode_query(42, "_acme-challenge.example.com").unwrap();
+        let msg = answer(
+            &query,
+            0,
+            &[
+                (QUESTION_NAME, 5, b"\x07example\x03com\x00"),
+                (QUESTION_NAME, 16, b"\x03abc"),
+            ],
+        );
+        assert_eq!(decode_answer(42, &msg).unwrap(), Some(vec!["abc".to_string()]));
+    }
+
+    #[test]
+    fn handles_missing_names_and_failures() {
+        let query = encode_query(42, "_acme-challenge.example.com").unwrap();
+        assert_eq!(decode_answer(42, &answer(&query, 3, &[])).unwrap(), Some(vec![]));
+        assert!(decode_answer(42, &answer(&query, 2, &[])).is_err());
+    }
+
+    #[test]
+    fn ignores_other_messages() {
+        let query = encode_query(42, "_acme-challenge.example.com").unwrap();
+        let msg = answer(&query, 0, &[(QUESTION_NAME, 16, b"\x03abc")]);
+        assert_eq!(decode_answer(43, &msg).unwrap(), None);
+        // The query itself is not an answer.
+        assert_eq!(decode_answer(42, &query).unwrap(), None);
+        assert_eq!(decode_answer(42, &msg[..8]).unwrap(), None);
+    }
+
+    #[test]
+    fn rejects_truncated_answers() {
+        let query = encode_query(42, "_acme-challenge.example.com").unwrap();
+        let msg = answer(&query, 0, &[(QUESTION_NAME, 16, b"\x03abc")]);
+        for len in 12..msg.len() {
+            assert!(decode_answer(42, &msg[..len]).is_err(), "length {}", len);
+        }
+        // A string longer than its record.
+        let msg = answer(&query, 0, &[(QUESTION_NAME, 16, b"\x05abc")]);
+        assert!(decode_answer(42, &msg).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_names() {
+        assert!(matches!(
+            encode_query(42, "example..com"),
+            Err(Error::ArgumentError(_))
+        ));
+        assert!(matches!(
+            encode_query(42, &format!("{}.com", "a".repeat(64))),
+            Err(Error::ArgumentError(_))
+        ));
+        assert!(encode_query(42, "example.com.").is_ok());
+    }
+
+    // Resolver on which `ns1` answers the challenge at once, `ns2` after
+    // `ns2_lookups` lookups, and `ns3` fails until then.
+    struct StubResolver {
+        ns2_lookups: usize,
+        lookups: AtomicUsize,
+    }
+
+    impl StubResolver {
+        fn new(ns2_lookups: usize) -> Self {
+            StubResolver {
+                ns2_lookups,
+                lookups: AtomicUsize::new(0),
+            }
+        }
+    }
+
+    impl TxtResolver for StubResolver {
+        fn lookup_txt<'a>(
+            &'a self,
+            server: &'a str,
+            name: &'a str,
+        ) -> BoxFuture<'a, Result<Vec<String>>> {
+            assert_eq!(name, "_acme-challenge.example.com");
+            let visible = match server {
+                "ns2" => self.lookups.fetch_add(1, Ordering::SeqCst) + 1 >= self.ns2_lookups,
+                "ns3" => {
+                    if self.lookups.load(Ordering::SeqCst) < self.ns2_lookups {
+                        return Box::pin(async { Err(malformed()) });
+                    }
+                    true
+                }
+                _ => true,
+            };
+            let records = if visible {
+                vec!["other".to_string(), "value".to_string()]
+            } else {
+                vec!["other".to_string()]
+            };
+            Box::pin(async move { Ok(records) })
+        }
+    }
+
+    fn name_servers() -> Vec<String> {
+        vec!["ns1".into(), "ns2".into(), "ns3".into()]
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn waits_for_all_name_servers() {
+        let solver = Dns01Solver::with_resolver(StubResolver::new(3))
+            .poll_interval(Duration::from_secs(5))
+            .propagation_timeout(Duration::from_secs(60));
+        let start = Instant::now();
+
+        solver
+            .wait_on(name_servers(), "_acme-challenge.example.com", "value")
+            .await
+            .unwrap();
+        assert_eq!(start.elapsed(), Duration::from_secs(10));
+        assert_eq!(solver.resolver.lookups.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn times_out() {
+        let solver = Dns01Solver::with_resolver(StubResolver::new(usize::MAX))
+            .poll_interval(Duration::from_secs(5))
+            .propagation_timeout(Duration::from_secs(12));
+        let start = Instant::now();
+
+        let err = solver
+            .wait_on(name_servers(), "_acme-challenge.example.com", "value")
+            .await
+            .unwrap_err();
+        match err {
+            Error::TimeoutError(message) => assert!(message.contains("ns2, ns3"), "{}", message),
+            e => panic!("unexpected error: {}", e),
+        }
+        assert_eq!(start.elapsed(), Duration::from_secs(10));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn waits_forever_without_deadline() {
+        let solver = Dns01Solver::with_resolver(StubResolver::new(3))
+            .poll_interval(Duration::from_secs(5))
+            .propagation_timeout(Duration::MAX);
+
+        solver
+            .wait_on(name_servers(), "_acme-challenge.example.com", "value")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn queries_over_udp() {
+        let server = UdpSocket::bind("127.0.0.1:0").await.unwrap();
+        let port = server.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let mut buf = [0; 512];
+            let (len, peer) = server.recv_from(&mut buf).await.unwrap();
+            let msg = answer(&buf[..len], 0, &[(QUESTION_NAME, 16, b"\x05value")]);
+            server.send_to(&msg, peer).await.unwrap();
+        });
+
+        let resolver = UdpResolver::new().port(port).timeout(Duration::MAX);
+        let records = resolver
+            .lookup_txt("127.0.0.1", "_acme-challenge.example.com")
+            .await
+            .unwrap();
+        assert_eq!(records, vec!["value".to_string()]);
+    }
+}
@@ -0,0 +1,344 @@
+//! Dynamic DNS (DynHost) records and logins, under
+//! `/domain/zone/{zone}/dynHost`.
+//!
+//! DynHost records are A records whose address can be updated by their
+//! logins, typically from a router or a small daemon such as the
+//! [`Updater`].
+
+use crate::{
+    client::{encode_path_segment, Error, OvhClient, Result},
+    domain::zone::{self, zone_path},
+};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+use std::net::IpAddr;
+
+/// DynHost record.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Record {
+    pub id: u64,
+    pub zone: String,
+    pub sub_domain: String,
+    pub ip: IpAddr,
+    pub ttl: Option<u32>,
+}
+
+/// DynHost record to create.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewRecord {
+    /// Sub-domain of the record, empty for the zone apex.
+    pub sub_domain: String,
+    pub ip: IpAddr,
+}
+
+/// Changes to apply to an existing DynHost record, unset fields being
+/// kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_domain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ip: Option<IpAddr>,
+}
+
+/// Login allowed to update DynHost records.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Login {
+    /// Full login, made of the zone and the login suffix.
+    pub login: String,
+    pub zone: String,
+    /// Sub-domains the login may update, `*` matching all of them.
+    pub sub_domain: String,
+}
+
+/// Login to create.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewLogin {
+    pub login_suffix: String,
+    pub password: String,
+    /// Sub-domains the login may update, `*` matching all of them.
+    pub sub_domain: String,
+}
+
+/// Changes to apply to an existing login, unset fields being kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoginUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_domain: Option<String>,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Filter<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    login: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sub_domain: Option<&'a str>,
+}
+
+fn record_path(zone: &str) -> String {
+    format!("{}/dynHost/record", zone_path(zone))
+}
+
+fn login_path(zone: &str) -> String {
+    format!("{}/dynHost/login", zone_path(zone))
+}
+
+/// Lists the IDs of the DynHost records of a zone, optionally only those
+/// of `sub_domain`.
+pub async fn list_records(
+    client: &OvhClient,
+    zone: &str,
+    sub_domain: Option<&str>,
+) -> Result<Vec<u64>> {
+    let filter = Filter {
+        login: None,
+        sub_domain,
+    };
+    client.get_json_with_query(&record_path(zone), &filter).await
+}
+
+/// Retrieves a DynHost record.
+pub async fn get_record(client: &OvhClient, zone: &str, id: u64) -> Result<Record> {
+    let path = format!("{}/{}", record_path(zone), id);
+    client.get_json(&path).await
+}
+
+/// Creates a DynHost record.
+///
+/// The change is only published once the zone is
+/// [refreshed](zone::refresh).
+pub async fn create_record(client: &OvhClient, zone: &str, record: &NewRecord) -> Result<Record> {
+    client.post_json(&record_path(zone), record).await
+}
+
+/// Updates a DynHost record.
+///
+/// The change is only published once the zone is
+/// [refreshed](zone::refresh).
+pub async fn update_record(
+    client: &OvhClient,
+    zone: &str,
+    id: u64,
+    update: &RecordUpdate,
+) -> Result<()> {
+    let path = format!("{}/{}", record_path(zone), id);
+    client.put_json(&path, update).await
+}
+
+/// Deletes a DynHost record.
+///
+/// The change is only published once the zone is
+/// [refreshed](zone::refresh).
+pub async fn delete_record(client: &OvhClient, zone: &str, id: u64) -> Result<()> {
+    let path = format!("{}/{}", record_path(zone), id);
+    client.delete_json(&path).await
+}
+
+/// Lists the DynHost logins of a zone, optionally filtered by login or
+/// sub-domain.
+pub async fn list_logins(
+    client: &OvhClient,
+    zone: &str,
+    login: Option<&str>,
+    sub_domain: Option<&str>,
+) -> Result<Vec<String>> {
+    let filter = Filter { login, sub_domain };
+    client.get_json_with_query(&login_path(zone), &filter).await
+}
+
+/// Retrieves a DynHost login.
+pub async fn get_login(client: &OvhClient, zone: &str, login: &str) -> Result<Login> {
+    let path = format!("{}/{}", login_path(zone), encode_path_segment(login));
+    client.get_json(&path).await
+}
+
+/// Creates a DynHost login.
+pub async fn create_login(client: &OvhClient, zone: &str, login: &NewLogin) -> Result<Login> {
+    client.post_json(&login_path(zone), login).await
+}
+
+/// Updates a DynHost login.
+pub async fn update_login(
+    client: &OvhClient,
+    zone: &str,
+    login: &str,
+    update: &LoginUpdate,
+) -> Result<()> {
+    let path = format!("{}/{}", login_path(zone), encode_path_segment(login));
+    client.put_json(&path, update).await
+}
+
+/// Deletes a DynHost login.
+pub async fn delete_login(client: &OvhClient, zone: &str, login: &str) -> Result<()> {
+    let path = format!("{}/{}", login_path(zone), encode_path_segment(login));
+    client.delete_json(&path).await
+}
+
+/// Changes the password of a DynHost login.
+pub async fn change_password(
+    client: &OvhClient,
+    zone: &str,
+    login: &str,
+    password: &str,
+) -> Result<()> {
+    #[derive(Serialize)]
+    struct Body<'a> {
+        password: &'a str,
+    }
+
+    let path = format!(
+        "{}/{}/changePassword",
+        login_path(zone),
+        encode_path_segment(login)
+    );
+    client.post_json(&path, &Body { password }).await
+}
+
+/// Source of the current public address.
+pub trait IpSource: Send + Sync {
+    fn current_ip(&self) -> BoxFuture<'_, Result<IpAddr>>;
+}
+
+/// Fixed address.
+impl IpSource for IpAddr {
+    fn current_ip(&self) -> BoxFuture<'_, Result<IpAddr>> {
+        Box::pin(async move { Ok(*self) })
+    }
+}
+
+/// Service answering the address of its clients as plain text.
+#[derive(Debug, Clone)]
+pub struct HttpIpSource {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl Default for HttpIpSource {
+    fn default() -> Self {
+        HttpIpSource::new("https://api.ipify.org")
+    }
+}
+
+impl HttpIpSource {
+    /// Uses the service at `url`.
+    pub fn new(url: &str) -> Self {
+        HttpIpSource {
+            client: reqwest::Client::new(),
+            url: url.into(),
+        }
+    }
+
+    /// Uses the given HTTP client, e.g. to bind to a given interface.
+    pub fn http_client(mut self, client: reqwest::Client) -> Self {
+        self.client = client;
+        self
+    }
+}
+
+impl IpSource for HttpIpSource {
+    fn current_ip(&self) -> BoxFuture<'_, Result<IpAddr>> {
+        Box::pin(async move {
+            let body = self
+                .client
+                .get(&self.url)
+                .send()
+                .await?
+                .error_for_status()?
+                .text()
+                .await?;
+            body.trim().parse().map_err(|e| Error::Error(Box::new(e)))
+        })
+    }
+}
+
+/// Keeps a DynHost record up to date with the current public address.
+///
+/// ```no_run
+/// # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+/// use ovh::domain::dynhost::{HttpIpSource, Updater};
+/// use std::time::Duration;
+///
+/// let mut updater = Updater::new(HttpIpSource::default(), "example.com", "home");
+/// loop {
+///     if let Some(ip) = updater.update(&client).await? {
+///         println!("home.example.com now points to {}", ip);
+///     }
+///     tokio::time::sleep(Duration::from_secs(300)).await;
+/// }
+/// # }
+/// ```
+pub struct Updater<S> {
+    source: S,
+    zone: String,
+    sub_domain: String,
+    last_ip: Option<IpAddr>,
+}
+
+impl<S: IpSource> Updater<S> {
+    /// Updates the DynHost record of `sub_domain` in `zone`, empty for the
+    /// zone apex.
+    pub fn new(source: S, zone: &str, sub_domain: &str) -> Self {
+        Updater {
+            source,
+            zone: zone.into(),
+            sub_domain: sub_domain.into(),
+            last_ip: None,
+        }
+    }
+
+    /// Last address known to be published.
+    pub fn last_ip(&self) -> Option<IpAddr> {
+        self.last_ip
+    }
+
+    /// Updates the record if the current address changed, creating it if
+    /// needed, and returns the new address if so.
+    ///
+    /// The record is only looked up on the first call and after an
+    /// address change.
+    pub async fn update(&mut self, client: &OvhClient) -> Result<Option<IpAddr>> {
+        let ip = self.source.current_ip().await?;
+        if self.last_ip == Some(ip) {
+            return Ok(None);
+        }
+
+        let ids = list_records(client, &self.zone, Some(&self.sub_domain)).await?;
+        let record = match ids.first() {
+            Some(id) => Some(get_record(client, &self.zone, *id).await?),
+            None => None,
+        };
+
+        let changed = match record {
+            Some(record) if record.ip == ip => false,
+            Some(record) => {
+                let update = RecordUpdate {
+                    ip: Some(ip),
+                    ..Default::default()
+                };
+                update_record(client, &self.zone, record.id, &update).await?;
+                true
+            }
+            None => {
+                let record = NewRecord {
+                    sub_domain: self.sub_domain.clone(),
+                    ip,
+                };
+                create_record(client, &self.zone, &record).await?;
+                true
+            }
+        };
+        if changed {
+            zone::refresh(client, &self.zone).await?;
+        }
+
+        self.last_ip = Some(ip);
+        Ok(Some(ip).filter(|_| changed))
+    }
+}
@@ -0,0 +1,313 @@
+//! DNS zones hosted by OVH, under `/domain/zone`.
+
+use crate::client::{
+    encode_path_segment, Error, Method, OvhClient, Result, Task, TaskOutcome, WaitOptions,
+};
+use futures::TryStreamExt;
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr};
+
+pub mod bind;
+pub mod reconcile;
+
+/// DNS zone.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Zone {
+    pub name: String,
+    pub name_servers: Vec<String>,
+    pub dnssec_supported: bool,
+    pub has_dns_anycast: bool,
+    pub last_update: String,
+}
+
+/// Type of a DNS record.
+///
+/// ```
+/// use ovh::domain::zone::RecordType;
+///
+/// let field_type: RecordType = "aaaa".parse().unwrap();
+/// assert_eq!(field_type, RecordType::Aaaa);
+/// assert_eq!(field_type.to_string(), "AAAA");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "UPPERCASE")]
+pub enum RecordType {
+    A,
+    Aaaa,
+    Caa,
+    Cname,
+    Dkim,
+    Dmarc,
+    Dname,
+    Https,
+    Loc,
+    Mx,
+    Naptr,
+    Ns,
+    Ptr,
+    Rp,
+    Spf,
+    Srv,
+    Sshfp,
+    Svcb,
+    Tlsa,
+    Txt,
+}
+
+const RECORD_TYPES: [(RecordType, &str); 20] = [
+    (RecordType::A, "A"),
+    (RecordType::Aaaa, "AAAA"),
+    (RecordType::Caa, "CAA"),
+    (RecordType::Cname, "CNAME"),
+    (RecordType::Dkim, "DKIM"),
+    (RecordType::Dmarc, "DMARC"),
+    (RecordType::Dname, "DNAME"),
+    (RecordType::Https, "HTTPS"),
+    (RecordType::Loc, "LOC"),
+    (RecordType::Mx, "MX"),
+    (RecordType::Naptr, "NAPTR"),
+    (RecordType::Ns, "NS"),
+    (RecordType::Ptr, "PTR"),
+    (RecordType::Rp, "RP"),
+    (RecordType::Spf, "SPF"),
+    (RecordType::Srv, "SRV"),
+    (RecordType::Sshfp, "SSHFP"),
+    (RecordType::Svcb, "SVCB"),
+    (RecordType::Tlsa, "TLSA"),
+    (RecordType::Txt, "TXT"),
+];
+
+impl RecordType {
+    /// Returns the usual upper-case name of the type, e.g. `CNAME`.
+    pub fn as_str(self) -> &'static str {
+        RECORD_TYPES
+            .iter()
+            .find(|(field_type, _)| *field_type == self)
+            .map(|(_, name)| *name)
+            .unwrap()
+    }
+}
+
+impl FromStr for RecordType {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        RECORD_TYPES
+            .iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(s))
+            .map(|(field_type, _)| *field_type)
+            .ok_or_else(|| Error::ArgumentError(format!("unknown record type `{}`", s)))
+    }
+}
+
+impl fmt::Display for RecordType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// DNS record of a zone.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Record {
+    pub id: u64,
+    pub zone: String,
+    /// Sub-domain of the record, empty for the zone apex.
+    pub sub_domain: String,
+    pub field_type: RecordType,
+    pub target: String,
+    /// TTL in seconds, `0` meaning the default TTL of the zone.
+    pub ttl: u32,
+}
+
+/// Record to be created in a zone.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewRecord {
+    pub field_type: RecordType,
+    /// Sub-domain of the record, empty for the zone apex.
+    pub sub_domain: String,
+    pub target: String,
+    /// TTL in seconds, the default TTL of the zone being used if unset.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u32>,
+}
+
+impl NewRecord {
+    /// Creates a record using the default TTL of the zone.
+    pub fn new(field_type: RecordType, sub_domain: &str, target: &str) -> Self {
+        NewRecord {
+            field_type,
+            sub_domain: sub_domain.into(),
+            target: target.into(),
+            ttl: None,
+        }
+    }
+
+    /// Sets the TTL of the record, in seconds.
+    pub fn ttl(mut self, ttl: u32) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+}
+
+/// Changes to apply to an existing record, unset fields being kept.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordUpdate {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sub_domain: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ttl: Option<u32>,
+}
+
+/// Filter for listing the records of a zone.
+///
+/// ```
+/// use ovh::domain::zone::{RecordFilter, RecordType};
+///
+/// let filter = RecordFilter::new()
+///     .field_type(RecordType::Mx)
+///     .sub_domain("");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordFilter {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    field_type: Option<RecordType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sub_domain: Option<String>,
+}
+
+impl RecordFilter {
+    /// Creates a filter matching every record.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only matches records of the given type.
+    pub fn field_type(mut self, field_type: RecordType) -> Self {
+        self.field_type = Some(field_type);
+        self
+    }
+
+    /// Only matches records of the given sub-domain, empty for the zone
+    /// apex.
+    pub fn sub_domain(mut self, sub_domain: &str) -> Self {
+        self.sub_domain = Some(sub_domain.into());
+        self
+    }
+}
+
+pub(super) fn zone_path(zone: &str) -> String {
+    format!("/domain/zone/{}", encode_path_segment(zone))
+}
+
+/// Lists the names of the zones of the account.
+pub async fn list_zones(client: &OvhClient) -> Result<Vec<String>> {
+    client.get_json("/domain/zone").await
+}
+
+/// Retrieves a zone.
+pub async fn get_zone(client: &OvhClient, zone: &str) -> Result<Zone> {
+    client.get_json(&zone_path(zone)).await
+}
+
+/// Lists the IDs of the records of a zone matching `filter`.
+pub async fn list_records(
+    client: &OvhClient,
+    zone: &str,
+    filter: &RecordFilter,
+) -> Result<Vec<u64>> {
+    let path = format!("{}/record", zone_path(zone));
+    client.get_json_with_query(&path, filter).await
+}
+
+/// Retrieves a record.
+pub async fn get_record(client: &OvhClient, zone: &str, id: u64) -> Result<Record> {
+    let path = format!("{}/record/{}", zone_path(zone), id);
+    client.get_json(&path).await
+}
+
+/// Retrieves the records of a zone matching `filter`.
+///
+/// ```no_run
+/// # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+/// use ovh::domain::zone::{self, RecordFilter, RecordType};
+///
+/// let filter = RecordFilter::new().field_type(RecordType::A);
+/// for record in zone::records(&client, "example.com", &filter).await? {
+///     println!("{} -> {}", record.sub_domain, record.target);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub async fn records(
+    client: &OvhClient,
+    zone: &str,
+    filter: &RecordFilter,
+) -> Result<Vec<Record>> {
+    let list_path = format!("{}/record", zone_path(zone));
+    let item_path = |id: &u64| format!("{}/record/{}", zone_path(zone), id);
+
+    // Fetching a few records at once, without hammering the API.
+    client
+        .list_and_fetch_with_query(&list_path, filter, item_path, 8)
+        .try_collect()
+        .await
+}
+
+/// Creates a record.
+///
+/// The change is only published once the zone is [refreshed](refresh).
+pub async fn create_record(client: &OvhClient, zone: &str, record: &NewRecord) -> Result<Record> {
+    let path = format!("{}/record", zone_path(zone));
+    client.post_json(&path, record).await
+}
+
+/// Updates a record.
+///
+/// The change is only published once the zone is [refreshed](refresh).
+pub async fn update_record(
+    client: &OvhClient,
+    zone: &str,
+    id: u64,
+    update: &RecordUpdate,
+) -> Result<()> {
+    let path = format!("{}/record/{}", zone_path(zone), id);
+    client.put_json(&path, update).await
+}
+
+/// Deletes a record.
+///
+/// The change is only published once the zone is [refreshed](refresh).
+pub async fn delete_record(client: &OvhClient, zone: &str, id: u64) -> Result<()> {
+    let path = format!("{}/record/{}", zone_path(zone), id);
+    client.delete_json(&path).await
+}
+
+/// Retrieves an asynchronous operation on a zone, such as an import.
+pub async fn get_task(client: &OvhClient, zone: &str, id: u64) -> Result<Task> {
+    let path = format!("{}/task/{}", zone_path(zone), id);
+    client.get_json(&path).await
+}
+
+/// Waits for an asynchronous operation on a zone to complete.
+pub async fn wait_for_task(
+    client: &OvhClient,
+    zone: &str,
+    id: u64,
+    options: &WaitOptions,
+) -> Result<TaskOutcome> {
+    let path = format!("{}/task/{}", zone_path(zone), id);
+    client.wait_for_task(&path, options).await
+}
+
+/// Publishes the pending changes of a zone.
+pub async fn refresh(client: &OvhClient, zone: &str) -> Result<()> {
+    let path = format!("{}/refresh", zone_path(zone));
+    client.request(Method::POST, &path).send_json().await
+}
@@ -0,0 +1,510 @@
+//! Zone files in the BIND format, as exported and imported by OVH.
+
+use super::{zone_path, RecordType};
+use crate::client::{Error, OvhClient, Result, Task};
+use serde::Serialize;
+use std::{fmt, str::FromStr};
+use thiserror::Error;
+
+/// Error found while parsing a zone file.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+#[error("line {line}: {message}")]
+pub struct ParseError {
+    /// Line of the zone file, starting at 1.
+    pub line: usize,
+    pub message: String,
+}
+
+/// Resource record of a zone file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZoneFileRecord {
+    /// Owner name, as written: relative, absolute or `@`.
+    pub name: String,
+    pub ttl: Option<u32>,
+    pub class: Option<String>,
+    /// Upper-case record type, e.g. `SOA` or `MX`.
+    pub record_type: String,
+    /// Record data, as written.
+    pub data: String,
+}
+
+impl ZoneFileRecord {
+    /// Returns the type of the record, if it can be managed through
+    /// the OVH API.
+    pub fn field_type(&self) -> Option<RecordType> {
+        self.record_type.parse().ok()
+    }
+}
+
+/// Entry of a zone file, in the order it appears.
+///
+/// Directives apply to the records following them, until the next
+/// directive of the same kind.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Entry {
+    /// `$ORIGIN` directive.
+    Origin(String),
+    /// `$TTL` directive, in seconds.
+    Ttl(u32),
+    Record(ZoneFileRecord),
+}
+
+/// Parsed zone file.
+///
+/// ```
+/// use ovh::domain::zone::bind::{Entry, ZoneFile};
+///
+/// let zone: ZoneFile = "
+/// $ORIGIN example.com.
+/// $TTL 1h
+/// @       IN SOA dns.ovh.net. tech.ovh.net. (
+///             2024010101 86400 3600 3600000 300 )
+///         IN NS   dns.ovh.net.
+/// www 600 IN A    203.0.113.10
+/// txt     IN TXT  \"v=spf1 -all\" ; comment
+/// ".parse().unwrap();
+///
+/// assert_eq!(zone.entries[0], Entry::Origin("example.com.".to_string()));
+/// assert_eq!(zone.entries[1], Entry::Ttl(3600));
+///
+/// let records: Vec<_> = zone.records().collect();
+/// assert_eq!(records.len(), 4);
+/// assert_eq!(records[1].name, "@");
+/// assert_eq!(records[2].ttl, Some(600));
+/// assert_eq!(records[3].data, "\"v=spf1 -all\"");
+///
+/// // Serializing gives back an equivalent zone file.
+/// assert_eq!(zone.to_string().parse::<ZoneFile>().unwrap(), zone);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ZoneFile {
+    pub entries: Vec<Entry>,
+}
+
+impl ZoneFile {
+    /// Returns the records of the zone file, without the directives.
+    pub fn records(&self) -> impl Iterator<Item = &ZoneFileRecord> {
+        self.entries.iter().filter_map(|entry| match entry {
+            Entry::Record(record) => Some(record),
+            _ => None,
+        })
+    }
+}
+
+/// Parses a TTL, either in seconds or with BIND units, e.g. `1h30m`.
+fn parse_ttl(s: &str) -> Option<u32> {
+    if let Ok(ttl) = s.parse() {
+        return Some(ttl);
+    }
+
+    let mut ttl: u32 = 0;
+    let mut value: Option<u32> = None;
+    for c in s.chars() {
+        if let Some(digit) = c.to_digit(10) {
+            value = Some(value.unwrap_or(0).checked_mul(10)?.checked_add(digit)?);
+            continue;
+        }
+
+        let unit = match c.to_ascii_lowercase() {
+            's' => 1,
+            'm' => 60,
+            'h' => 3600,
+            'd' => 86400,
+            'w' => 604_800,
+            _ => return None,
+        };
+        ttl = ttl.checked_add(value.take()?.checked_mul(unit)?)?;
+    }
+
+    match value {
+        Some(_) => None,
+        None => Some(ttl),
+    }
+}
+
+fn is_class(s: &str) -> bool {
+    ["IN", "CH", "HS", "CS"]
+        .iter()
+        .any(|class| class.eq_ignore_ascii_case(s))
+}
+
+/// Logical line, i.e. physical lines joined over parentheses, without
+/// comments.
+struct Line {
+    number: usize,
+    has_owner: bool,
+    tokens: Vec<String>,
+}
+
+fn tokenize(input: &str) -> std::result::Result<Vec<Line>, ParseError> {
+    let mut lines = Vec::new();
+    let mut current: Option<Line> = None;
+    let mut depth = 0;
+
+    for (i, text) in input.lines().enumerate() {
+        let number = i + 1;
+        let line = current.get_or_insert_with(|| Line {
+            number,
+            has_owner: !text.starts_with(|c: char| c.is_whitespace()),
+            tokens: Vec::new(),
+        });
+
+        let mut token: Option<String> = None;
+        let mut quoted = false;
+        let mut escaped = false;
+
+        for c in text.chars() {
+            if quoted {
+                let token = token.get_or_insert_with(String::new);
+                token.push(c);
+                if escaped {
+                    escaped = false;
+                } else if c == '\\' {
+                    escaped = true;
+                } else if c == '"' {
+                    quoted = false;
+                }
+                continue;
+            }
+
+            if escaped {
+                token.get_or_insert_with(String::new).push(c);
+                escaped = false;
+                continue;
+            }
+
+            match c {
+                ';' => break,
+                // Escaped characters are kept as written, but lose their
+                // special meaning.
+                '\\' => {
+                    token.get_or_insert_with(String::new).push(c);
+                    escaped = true;
+                }
+                '"' => {
+                    token.get_or_insert_with(String::new).push(c);
+                    quoted = true;
+                }
+                '(' | ')' => {
+                    line.tokens.extend(token.take());
+                    depth += if c == '(' { 1 } else { -1 };
+                    if depth < 0 {
+                        return Err(ParseError {
+                            line: number,
+                            message: "unbalanced `)`".to_string(),
+                        });
+                    }
+                }
+                c if c.is_whitespace() => line.tokens.extend(token.take()),
+                c => token.get_or_insert_with(String::new).push(c),
+            }
+        }
+
+        if quoted {
+            return Err(ParseError {
+                line: number,
+                message: "unterminated quoted string".to_string(),
+            });
+        }
+        if escaped {
+            return Err(ParseError {
+                line: number,
+                message: "escape at end of line".to_string(),
+            });
+        }
+        line.tokens.extend(token.take());
+
+        if depth == 0 {
+            lines.extend(current.take());
+        }
+    }
+
+    match current {
+        Some(line) => Err(ParseError {
+            line: line.number,
+            message: "unbalanced `(`".to_string(),
+        }),
+        None => Ok(lines),
+    }
+}
+
+/// Parses a zone file.
+///
+/// `$ORIGIN` and `$TTL` directives are supported and kept in order;
+/// owner names and data are kept as written, without being made
+/// absolute.
+pub fn parse(input: &str) -> std::result::Result<ZoneFile, ParseError> {
+    let mut zone = ZoneFile::default();
+    let mut last_owner: Option<String> = None;
+
+    for line in tokenize(input)? {
+        let number = line.number;
+        let error = |message: &str| ParseError {
+            line: number,
+            message: message.to_string(),
+        };
+
+        let mut tokens = line.tokens.into_iter().peekable();
+        let first = match tokens.peek() {
+            Some(first) => first.clone(),
+            None => continue,
+        };
+
+        if line.has_owner && first.starts_with('$') {
+            tokens.next();
+            let value = tokens
+                .next()
+                .ok_or_else(|| error(&format!("missing value for `{}`", first)))?;
+
+            match first.to_ascii_uppercase().as_str() {
+                "$ORIGIN" => zone.entries.push(Entry::Origin(value)),
+                "$TTL" => {
+                    let ttl = parse_ttl(&value).ok_or_else(|| error("invalid TTL"))?;
+                    zone.entries.push(Entry::Ttl(ttl));
+                }
+                _ => return Err(error(&format!("unsupported directive `{}`", first))),
+            }
+            continue;
+        }
+
+        let name = if line.has_owner {
+            tokens.next().unwrap()
+        } else {
+            last_owner
+                .clone()
+                .ok_or_else(|| error("missing owner name"))?
+        };
+
+        let mut ttl = None;
+        let mut class = None;
+        let record_type = loop {
+            let token = tokens.next().ok_or_else(|| error("missing record type"))?;
+
+            if ttl.is_none() && token.starts_with(|c: char| c.is_ascii_digit()) {
+                ttl = Some(parse_ttl(&token).ok_or_else(|| error("invalid TTL"))?);
+            } else if class.is_none() && is_class(&token) {
+                class = Some(token.to_ascii_uppercase());
+            } else if token.chars().all(|c| c.is_ascii_alphanumeric()) {
+                break token.to_ascii_uppercase();
+            } else {
+                return Err(error(&format!("invalid record type `{}`", token)));
+            }
+        };
+
+        let data = tokens.collect::<Vec<_>>().join(" ");
+        if data.is_empty() {
+            return Err(error("missing record data"));
+        }
+
+        last_owner = Some(name.clone());
+        zone.entries.push(Entry::Record(ZoneFileRecord {
+            name,
+            ttl,
+            class,
+            record_type,
+            data,
+        }));
+    }
+
+    Ok(zone)
+}
+
+impl FromStr for ZoneFile {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        parse(s)
+    }
+}
+
+impl fmt::Display for ZoneFileRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\t", self.name)?;
+        if let Some(ttl) = self.ttl {
+            write!(f, "{}\t", ttl)?;
+        }
+        if let Some(class) = &self.class {
+            write!(f, "{}\t", class)?;
+        }
+        write!(f, "{}\t{}", self.record_type, self.data)
+    }
+}
+
+impl fmt::Display for ZoneFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for entry in &self.entries {
+            match entry {
+                Entry::Origin(origin) => writeln!(f, "$ORIGIN {}", origin)?,
+                Entry::Ttl(ttl) => writeln!(f, "$TTL {}", ttl)?,
+                Entry::Record(record) => writeln!(f, "{}", record)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Retrieves a zone as a BIND zone file, without parsing it.
+pub async fn export_text(client: &OvhClient, zone: &str) -> Result<String> {
+    let path = format!("{}/export", zone_path(zone));
+    client.get_json(&path).await
+}
+
+/// Retrieves and parses a zone.
+pub async fn export(client: &OvhClient, zone: &str) -> Result<ZoneFile> {
+    let text = export_text(client, zone).await?;
+    parse(&text).map_err(|e| Error::Error(e.into()))
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct Import<'a> {
+    zone_file: &'a str,
+}
+
+/// Replaces a zone with the content of a BIND zone file.
+///
+/// The zone file is parsed before being sent, so that invalid files are
+/// rejected locally, as an [`Error::ArgumentError`] giving the line at
+/// fault.
+pub async fn import_text(client: &OvhClient, zone: &str, zone_file: &str) -> Result<Task> {
+    parse(zone_file).map_err(|e| Error::ArgumentError(format!("zone file {}", e)))?;
+
+    let path = format!("{}/import", zone_path(zone));
+    client.post_json(&path, &Import { zone_file }).await
+}
+
+/// Replaces a zone with the given records.
+///
+/// ```no_run
+/// # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+/// use ovh::domain::zone::bind;
+///
+/// let zone_file = std::fs::read_to_string("example.com.zone").unwrap();
+/// let zone_file: bind::ZoneFile = zone_file.parse().unwrap();
+/// let task = bind::import(&client, "example.com", &zone_file).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn import(client: &OvhClient, zone: &str, zone_file: &ZoneFile) -> Result<Task> {
+    import_text(client, zone, &zone_file.to_string()).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn records(zone: &ZoneFile) -> Vec<(&str, &str)> {
+        zone.records()
+            .map(|r| (r.name.as_str(), r.data.as_str()))
+            .collect()
+    }
+
+    #[test]
+    fn keeps_directives_in_order() {
+        let input = "\
+$ORIGIN example.com.
+www A 203.0.113.1
+$ORIGIN sub.example.com.
+www A 203.0.113.2
+$TTL 300
+api A 203.0.113.3
+";
+        let zone = parse(input).unwrap();
+        assert_eq!(
+            zone.entries,
+            vec![
+                Entry::Origin("example.com.".to_string()),
+                Entry::Record(ZoneFileRecord {
+                    name: "www".to_string(),
+                    ttl: None,
+                    class: None,
+                    record_type: "A".to_string(),
+                    data: "203.0.113.1".to_string(),
+                }),
+                Entry::Origin("sub.example.com.".to_string()),
+                Entry::Record(ZoneFileRecord {
+                    name: "www".to_string(),
+                    ttl: None,
+                    class: None,
+                    record_type: "A".to_string(),
+                    data: "203.0.113.2".to_string(),
+                }),
+                Entry::Ttl(300),
+                Entry::Record(ZoneFileRecord {
+                    name: "api".to_string(),
+                    ttl: None,
+                    class: None,
+                    record_type: "A".to_string(),
+                    data: "203.0.113.3".to_string(),
+                }),
+            ]
+        );
+
+        let output = zone.to_string();
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines[0], "$ORIGIN example.com.");
+        assert_eq!(lines[1], "www\tA\t203.0.113.1");
+        assert_eq!(lines[2], "$ORIGIN sub.example.com.");
+        assert_eq!(lines[3], "www\tA\t203.0.113.2");
+        assert_eq!(lines[4], "$TTL 300");
+        assert_eq!(parse(&output).unwrap(), zone);
+    }
+
+    #[test]
+    fn keeps_escaped_characters() {
+        let zone = parse("www IN TXT v=a\\;b ; comment\nx IN TXT a\\ b \\(c\\)\n").unwrap();
+        assert_eq!(records(&zone), vec![("www", "v=a\\;b"), ("x", "a\\ b \\(c\\)")]);
+
+        let zone = parse("www IN TXT \"a\\\"; b\"\n").unwrap();
+        assert_eq!(records(&zone), vec![("www", "\"a\\\"; b\"")]);
+    }
+
+    #[test]
+    fn rejects_escape_at_end_of_line() {
+        let error = parse("www IN TXT a\\\n").unwrap_err();
+        assert_eq!(error.line, 1);
+    }
+
+    #[test]
+    fn rejects_unbalanced_parentheses() {
+        let error = parse("@ IN SOA a. b. ( 1 2 3 4 5\nwww A 203.0.113.1\n").unwrap_err();
+        assert_eq!(error.line, 1);
+        assert_eq!(error.message, "unbalanced `(`");
+
+        let error = parse("www A 203.0.113.1\n@ IN SOA a. b. 1 2 3 4 5 )\n").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.message, "unbalanced `)`");
+    }
+
+    #[test]
+    fn joins_lines_within_parentheses() {
+        let zone = parse("@ IN SOA a. b. (\n  1 ; serial\n  2 3 4 5 )\n").unwrap();
+        assert_eq!(records(&zone), vec![("@", "a. b. 1 2 3 4 5")]);
+    }
+
+    #[test]
+    fn rejects_unterminated_quotes() {
+        let error = parse("www A 203.0.113.1\ntxt IN TXT \"v=spf1 -all\n").unwrap_err();
+        assert_eq!(error.line, 2);
+        assert_eq!(error.message, "unterminated quoted string");
+    }
+
+    #[test]
+    fn parses_ttl_units() {
+        assert_eq!(parse_ttl("3600"), Some(3600));
+        assert_eq!(parse_ttl("1h30m"), Some(5400));
+        assert_eq!(parse_ttl("1W"), Some(604_800));
+        assert_eq!(parse_ttl("1h30"), None);
+        assert_eq!(parse_ttl("1y"), None);
+    }
+
+    #[tokio::test]
+    async fn rejects_invalid_imports_locally() {
+        let client = OvhClient::new("http://127.0.0.1:1/1.0", "key", "secret", "consumer").unwrap();
+        let result = import_text(&client, "example.com", "www IN A 203.0.113.1\n( IN A\n").await;
+        match result {
+            Err(Error::ArgumentError(message)) => assert!(message.starts_with("zone file line 2:")),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+}
@@ -0,0 +1,382 @@
+//! Declarative management of the records of a zone.
+//!
+//! The records wanted in a zone are compared with the existing ones to
+//! compute a [`Plan`], which can be reviewed before being applied.
+
+use super::{
+    create_record, delete_record, records, refresh, update_record, NewRecord, Record,
+    RecordFilter, RecordType, RecordUpdate,
+};
+use crate::client::{OvhClient, Result};
+use std::{collections::BTreeMap, fmt};
+
+/// Change of an existing record.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Update {
+    pub record: Record,
+    pub update: RecordUpdate,
+}
+
+/// Changes needed to get from the existing records of a zone to the
+/// wanted ones.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Plan {
+    pub zone: String,
+    pub create: Vec<NewRecord>,
+    pub update: Vec<Update>,
+    pub delete: Vec<Record>,
+    pub unchanged: Vec<Record>,
+}
+
+type Key = (RecordType, String);
+
+fn wanted_ttl(record: &NewRecord) -> u32 {
+    // The API reports the default TTL of the zone as 0.
+    record.ttl.unwrap_or(0)
+}
+
+/// Computes the changes needed to get from `existing` records to
+/// `desired` ones.
+///
+/// Records are matched by type and sub-domain, then by target: records
+/// only differing by their target or TTL are updated rather than
+/// replaced. An unset TTL stands for the default TTL of the zone.
+///
+/// NS records of the zone apex are left alone, unless some are desired.
+/// A record desired several times is only kept once.
+///
+/// ```
+/// use ovh::domain::zone::{reconcile, NewRecord, Record, RecordType};
+///
+/// let existing = vec![
+///     Record {
+///         id: 1,
+///         zone: "example.com".into(),
+///         sub_domain: "www".into(),
+///         field_type: RecordType::A,
+///         target: "203.0.113.10".into(),
+///         ttl: 0,
+///     },
+///     Record {
+///         id: 2,
+///         zone: "example.com".into(),
+///         sub_domain: "old".into(),
+///         field_type: RecordType::Cname,
+///         target: "www".into(),
+///         ttl: 0,
+///     },
+/// ];
+/// let desired = vec![
+///     NewRecord::new(RecordType::A, "www", "203.0.113.20"),
+///     NewRecord::new(RecordType::Txt, "", "\"v=spf1 -all\"").ttl(600),
+/// ];
+///
+/// let plan = reconcile::diff("example.com", existing, &desired);
+/// assert_eq!(plan.create, vec![desired[1].clone()]);
+/// assert_eq!(plan.update.len(), 1);
+/// assert_eq!(plan.update[0].record.id, 1);
+/// assert_eq!(plan.delete.len(), 1);
+/// assert_eq!(plan.delete[0].id, 2);
+/// assert!(plan.unchanged.is_empty());
+/// ```
+pub fn diff(zone: &str, existing: Vec<Record>, desired: &[NewRecord]) -> Plan {
+    let mut plan = Plan {
+        zone: zone.into(),
+        ..Default::default()
+    };
+
+    let manage_apex_ns = desired
+        .iter()
+        .any(|r| r.field_type == RecordType::Ns && r.sub_domain.is_empty());
+
+    let mut groups: BTreeMap<Key, (Vec<Record>, Vec<&NewRecord>)> = BTreeMap::new();
+    for record in existing {
+        if !manage_apex_ns && record.field_type == RecordType::Ns && record.sub_domain.is_empty() {
+            continue;
+        }
+        let key = (record.field_type, record.sub_domain.clone());
+        groups.entry(key).or_default().0.push(record);
+    }
+    for record in desired {
+        let key = (record.field_type, record.sub_domain.clone());
+        let wanted = &mut groups.entry(key).or_default().1;
+        if wanted.iter().all(|r| r.target != record.target) {
+            wanted.push(record);
+        }
+    }
+
+    for (_, (mut existing, desired)) in groups {
+        let mut remaining = Vec::new();
+
+        // Records with the same target are kept, fixing their TTL if
+        // needed.
+        for wanted in desired {
+            match existing.iter().position(|r| r.target == wanted.target) {
+                Some(i) => {
+                    let record = existing.remove(i);
+                    if record.ttl == wanted_ttl(wanted) {
+                        plan.unchanged.push(record);
+                    } else {
+                        let update = RecordUpdate {
+                            ttl: Some(wanted_ttl(wanted)),
+                            ..Default::default()
+                        };
+                        plan.update.push(Update { record, update });
+                    }
+                }
+                None => remaining.push(wanted),
+            }
+        }
+
+        // Other existing records are reused for the other targets.
+        let mut existing = existing.into_iter();
+        for wanted in remaining {
+            match existing.next() {
+                Some(record) => {
+                    let ttl = wanted_ttl(wanted);
+                    let update = RecordUpdate {
+                        target: Some(wanted.target.clone()),
+                        ttl: Some(ttl).filter(|ttl| *ttl != record.ttl),
+                        ..Default::default()
+                    };
+                    plan.update.push(Update { record, update });
+                }
+                None => plan.create.push(wanted.clone()),
+            }
+        }
+        plan.delete.extend(existing);
+    }
+
+    plan
+}
+
+/// Computes the changes needed to get the records of `zone` to the
+/// `desired` ones; see [`diff`].
+///
+/// ```no_run
+/// # async fn example(client: ovh::client::OvhClient) -> ovh::client::Result<()> {
+/// use ovh::domain::zone::{reconcile, NewRecord, RecordType};
+///
+/// let desired = vec![
+///     NewRecord::new(RecordType::A, "", "203.0.113.10"),
+///     NewRecord::new(RecordType::Cname, "www", "example.com."),
+/// ];
+///
+/// let plan = reconcile::plan(&client, "example.com", &desired).await?;
+/// print!("{}", plan);
+/// plan.apply(&client).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn plan(client: &OvhClient, zone: &str, desired: &[NewRecord]) -> Result<Plan> {
+    let existing = records(client, zone, &RecordFilter::new()).await?;
+    Ok(diff(zone, existing, desired))
+}
+
+impl Plan {
+    /// Whether the zone already has the desired records.
+    pub fn is_empty(&self) -> bool {
+        self.create.is_empty() && self.update.is_empty() && self.delete.is_empty()
+    }
+
+    /// Applies the changes, then refreshes the zone once.
+    ///
+    /// Records are deleted first, so that they do not conflict with the
+    /// created ones. Nothing is done if the plan is empty.
+    pub async fn apply(&self, client: &OvhClient) -> Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        for record in &self.delete {
+            delete_record(client, &self.zone, record.id).await?;
+        }
+        for Update { record, update } in &self.update {
+            update_record(client, &self.zone, record.id, update).await?;
+        }
+        for record in &self.create {
+            create_record(client, &self.zone, record).await?;
+        }
+
+        refresh(client, &self.zone).await
+    }
+}
+
+fn name(sub_domain: &str) -> &str {
+    if sub_domain.is_empty() {
+        "@"
+    } else {
+        sub_domain
+    }
+}
+
+impl fmt::Display for Plan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for r in &self.delete {
+            writeln!(f, "- {} {} {} (ttl {})", name(&r.sub_domain), r.field_type, r.target, r.ttl)?;
+        }
+        for Update { record: r, update } in &self.update {
+            let target = update.target.as_deref().unwrap_or(&r.target);
+            let ttl = update.ttl.unwrap_or(r.ttl);
+            writeln!(
+                f,
+                "~ {} {} {} (ttl {}) -> {} (ttl {})",
+                name(&r.sub_domain),
+                r.field_type,
+                r.target,
+                r.ttl,
+                target,
+                ttl
+            )?;
+        }
+        for r in &self.create {
+            writeln!(
+                f,
+                "+ {} {} {} (ttl {})",
+                name(&r.sub_domain),
+                r.field_type,
+                r.target,
+                wanted_ttl(r)
+            )?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(id: u64, field_type: RecordType, sub_domain: &str, target: &str, ttl: u32) -> Record {
+        Record {
+            id,
+            zone: "example.com".into(),
+            sub_domain: sub_domain.into(),
+            field_type,
+            target: target.into(),
+            ttl,
+        }
+    }
+
+    fn apex_ns() -> Vec<Record> {
+        vec![
+            record(1, RecordType::Ns, "", "dns1.ovh.net.", 0),
+            record(2, RecordType::Ns, "", "ns1.ovh.net.", 0),
+        ]
+    }
+
+    #[test]
+    fn leaves_apex_ns_alone() {
+        let mut existing = apex_ns();
+        existing.push(record(3, RecordType::Ns, "sub", "ns.example.net.", 0));
+
+        let plan = diff("example.com", existing, &[]);
+        assert!(plan.unchanged.is_empty());
+        assert_eq!(plan.delete.len(), 1);
+        assert_eq!(plan.delete[0].id, 3);
+    }
+
+    #[test]
+    fn manages_apex_ns_when_desired() {
+        let desired = vec![NewRecord::new(RecordType::Ns, "", "dns1.ovh.net.")];
+
+        let plan = diff("example.com", apex_ns(), &desired);
+        assert_eq!(plan.unchanged.len(), 1);
+        assert_eq!(plan.unchanged[0].id, 1);
+        assert_eq!(plan.delete.len(), 1);
+        assert_eq!(plan.delete[0].id, 2);
+        assert!(plan.create.is_empty() && plan.update.is_empty());
+    }
+
+    #[test]
+    fn updates_ttl_only() {
+        let existing = vec![record(1, RecordType::A, "www", "203.0.113.10", 3600)];
+        let desired = vec![NewRecord::new(RecordType::A, "www", "203.0.113.10").ttl(60)];
+
+        let plan = diff("example.com", existing, &desired);
+        assert_eq!(plan.update.len(), 1);
+        assert_eq!(plan.update[0].record.id, 1);
+        assert_eq!(
+            plan.update[0].update,
+            RecordUpdate {
+                ttl: Some(60),
+                ..Default::default()
+            }
+        );
+        assert!(plan.create.is_empty() && plan.delete.is_empty());
+    }
+
+    #[test]
+    fn unset_ttl_is_zone_default() {
+        let existing = vec![
+            record(1, RecordType::A, "www", "203.0.113.10", 0),
+            record(2, RecordType::A, "api", "203.0.113.10", 3600),
+        ];
+        let desired = vec![
+            NewRecord::new(RecordType::A, "www", "203.0.113.10"),
+            NewRecord::new(RecordType::A, "api", "203.0.113.10"),
+        ];
+
+        let plan = diff("example.com", existing, &desired);
+        assert_eq!(plan.unchanged.len(), 1);
+        assert_eq!(plan.unchanged[0].id, 1);
+        assert_eq!(plan.update.len(), 1);
+        assert_eq!(plan.update[0].record.id, 2);
+        assert_eq!(plan.update[0].update.ttl, Some(0));
+    }
+
+    #[test]
+    fn reuses_records_for_new_targets() {
+        let existing = vec![
+            record(1, RecordType::A, "www", "203.0.113.10", 0),
+            record(2, RecordType::A, "www", "203.0.113.11", 600),
+        ];
+        let desired = vec![
+            NewRecord::new(RecordType::A, "www", "203.0.113.11").ttl(600),
+            NewRecord::new(RecordType::A, "www", "203.0.113.20"),
+            NewRecord::new(RecordType::A, "www", "203.0.113.21").ttl(60),
+        ];
+
+        let plan = diff("example.com", existing, &desired);
+        assert_eq!(plan.unchanged.len(), 1);
+        assert_eq!(plan.unchanged[0].id, 2);
+        // The TTL is left out of the update when it does not change.
+        assert_eq!(plan.update.len(), 1);
+        assert_eq!(plan.update[0].record.id, 1);
+        assert_eq!(
+            plan.update[0].update,
+            RecordUpdate {
+                target: Some("203.0.113.20".into()),
+                ..Default::default()
+            }
+        );
+        assert_eq!(plan.create, vec![desired[2].clone()]);
+        assert!(plan.delete.is_empty());
+    }
+
+    #[test]
+    fn keeps_duplicates_once() {
+        let existing = vec![record(1, RecordType::Mx, "", "10 mx1.example.com.", 0)];
+        let desired = vec![
+            NewRecord::new(RecordType::Mx, "", "10 mx1.example.com."),
+            NewRecord::new(RecordType::Mx, "", "10 mx1.example.com."),
+            NewRecord::new(RecordType::Mx, "", "20 mx2.example.com."),
+            NewRecord::new(RecordType::Mx, "", "20 mx2.example.com.").ttl(60),
+        ];
+
+        let plan = diff("example.com", existing, &desired);
+        assert_eq!(plan.unchanged.len(), 1);
+        assert_eq!(plan.create, vec![desired[2].clone()]);
+        assert!(plan.update.is_empty() && plan.delete.is_empty());
+    }
+
+    #[test]
+    fn empty_plan_when_up_to_date() {
+        let existing = vec![record(1, RecordType::A, "", "203.0.113.10", 0)];
+        let desired = vec![NewRecord::new(RecordType::A, "", "203.0.113.10")];
+
+        let plan = diff("example.com", existing, &desired);
+        assert!(plan.is_empty());
+        assert_eq!(plan.to_string(), "");
+    }
+}
@@ -1,4 +1,6 @@
 //! Async client for the OVH API.
 
 pub mod client;
+pub mod dedicated;
+pub mod domain;
  
\ No newline at end of file